@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use crate::prelude::*;
+
+/// Known correct answers, stored one per line as `DAY PART ANSWER`.
+#[derive(Debug, Default)]
+pub struct Answers {
+    known: HashMap<(usize, usize), String>,
+}
+
+impl Answers {
+    /// Load the answers at `path`. A missing file is treated as an empty store.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("read answers from {}", path.display()))?;
+        content.parse()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, self.to_string())
+            .with_context(|| format!("write answers to {}", path.display()))
+    }
+
+    pub fn insert(&mut self, day: usize, part: usize, answer: &str) {
+        self.known.insert((day, part), answer.to_owned());
+    }
+
+    pub fn get(&self, day: usize, part: usize) -> Option<&str> {
+        self.known.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn verify(&self, day: usize, part: usize, answer: usize) -> Status {
+        match self.get(day, part) {
+            Some(known) if known == answer.to_string() => Status::Correct,
+            Some(known) => Status::Incorrect(known.to_owned()),
+            None => Status::Unknown,
+        }
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut known = HashMap::default();
+        for (i, line) in s.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let mut parts = line.split_whitespace();
+            let mut next = |name| {
+                parts
+                    .next()
+                    .ok_or_else(|| anyhow!("line {}: missing {}", i + 1, name))
+            };
+            let day = next("day")?.parse::<usize>().context("parse day")?;
+            let part = next("part")?.parse::<usize>().context("parse part")?;
+            let answer = next("answer")?;
+            known.insert((day, part), answer.to_owned());
+        }
+        Ok(Answers { known })
+    }
+}
+
+impl std::fmt::Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut entries = self.known.iter().collect::<Vec<_>>();
+        entries.sort();
+        for ((day, part), answer) in entries {
+            writeln!(f, "{} {} {}", day, part, answer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether a computed answer matches the known one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Correct,
+    Incorrect(String),
+    Unknown,
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Status::Correct => write!(f, "ok"),
+            Status::Incorrect(expected) => write!(f, "WRONG (expected {})", expected),
+            Status::Unknown => write!(f, "?"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn verify() {
+        let answers = "1 1 7\n1 2 5\n\n2 1 150\n".parse::<Answers>().unwrap();
+        assert_eq!(answers.verify(1, 1, 7), Status::Correct);
+        assert_eq!(answers.verify(1, 2, 6), Status::Incorrect("5".to_owned()));
+        assert_eq!(answers.verify(2, 2, 900), Status::Unknown);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut answers = Answers::default();
+        answers.insert(10, 2, "288957");
+        answers.insert(1, 1, "7");
+        assert_eq!(answers.to_string(), "1 1 7\n10 2 288957\n");
+        let parsed = answers.to_string().parse::<Answers>().unwrap();
+        assert_eq!(parsed.get(10, 2), Some("288957"));
+    }
+
+    #[test]
+    fn malformed() {
+        assert!("1 1".parse::<Answers>().is_err());
+        assert!("x 1 7".parse::<Answers>().is_err());
+    }
+}
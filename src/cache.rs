@@ -0,0 +1,125 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::prelude::*;
+
+/// A directory of cached files, addressed by slash-separated keys such as `input/2021/1`.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Cache {
+            dir: dir.as_ref().to_owned(),
+        }
+    }
+
+    pub fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(key)
+    }
+
+    /// Read the entry for `key`, if it has been cached.
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        let full_path = self.path(key);
+        if !full_path.exists() {
+            return Ok(None);
+        }
+        std::fs::read_to_string(&full_path)
+            .with_context(|| format!("read cache entry {}", full_path.display()))
+            .map(Some)
+    }
+
+    /// Read the entry for `key`, populating it with `f` on a miss. Nothing is written if `f`
+    /// fails.
+    pub fn get_or_insert_with<F>(&self, key: &str, f: F) -> Result<String>
+    where
+        F: FnOnce() -> Result<String>,
+    {
+        if let Some(content) = self.get(key)? {
+            return Ok(content);
+        }
+        let full_path = self.path(key);
+        let content =
+            f().with_context(|| format!("populate cache entry for {}", full_path.display()))?;
+        self.insert(key, &content)?;
+        Ok(content)
+    }
+
+    pub fn insert(&self, key: &str, content: &str) -> Result<()> {
+        let full_path = self.path(key);
+        let dir = full_path.parent().expect("non-empty path");
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(&full_path, content)
+            .with_context(|| format!("write cache entry {}", full_path.display()))
+    }
+
+    /// Remove the entry for `key`, returning whether it existed.
+    pub fn remove(&self, key: &str) -> Result<bool> {
+        let full_path = self.path(key);
+        if !full_path.exists() {
+            return Ok(false);
+        }
+        std::fs::remove_file(&full_path)
+            .with_context(|| format!("remove cache entry {}", full_path.display()))?;
+        Ok(true)
+    }
+
+    /// The names of the entries directly under `prefix`.
+    pub fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        let dir = self.path(prefix);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut names = Vec::new();
+        for entry in std::fs::read_dir(&dir).with_context(|| format!("list {}", dir.display()))? {
+            let entry = entry?;
+            if entry.file_type()?.is_file() {
+                names.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn temp_cache(name: &str) -> Cache {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        Cache::new(dir)
+    }
+
+    #[test]
+    fn get_or_insert_with() {
+        let cache = temp_cache("insert");
+        assert_eq!(cache.get("input/2021/1").unwrap(), None);
+        let content = cache
+            .get_or_insert_with("input/2021/1", || Ok("1\n2\n".to_owned()))
+            .unwrap();
+        assert_eq!(content, "1\n2\n");
+        // A second lookup is served from the cache.
+        let content = cache
+            .get_or_insert_with("input/2021/1", || Err(anyhow!("fetched twice")))
+            .unwrap();
+        assert_eq!(content, "1\n2\n");
+        assert_eq!(cache.list("input/2021").unwrap(), vec!["1"]);
+
+        assert!(cache.remove("input/2021/1").unwrap());
+        assert!(!cache.remove("input/2021/1").unwrap());
+        assert!(cache.list("input/2021").unwrap().is_empty());
+    }
+
+    #[test]
+    fn failed_populate_is_not_cached() {
+        let cache = temp_cache("failed");
+        assert!(cache
+            .get_or_insert_with("input/2021/2", || Err(anyhow!("offline")))
+            .is_err());
+        assert_eq!(cache.get("input/2021/2").unwrap(), None);
+    }
+}
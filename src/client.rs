@@ -0,0 +1,524 @@
+use std::io::Read;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::prelude::*;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies this tool to the AoC servers, as requested in the automation guidelines.
+const USER_AGENT: &str = concat!(
+    "github.com/cwbriones/advent-of-code-21 ",
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+);
+
+/// The minimum time between two requests made by the same client.
+const MIN_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The ways a request to the AoC servers can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+    /// The puzzle doesn't exist or hasn't unlocked yet.
+    NotUnlocked,
+    /// The server rejected the session token or sent us to the login page.
+    SessionExpired,
+    /// Any other unexpected response.
+    Status(u16),
+    /// The server couldn't be reached.
+    Network(String),
+}
+
+impl std::fmt::Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FetchError::NotUnlocked => write!(f, "puzzle is not unlocked yet"),
+            FetchError::SessionExpired => write!(f, "session token is invalid or expired"),
+            FetchError::Status(code) => write!(f, "unexpected status code: {}", code),
+            FetchError::Network(msg) => write!(f, "network error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+/// An HTTP client for the AoC site that identifies itself and throttles its requests.
+#[derive(Debug)]
+pub struct Client {
+    base_url: String,
+    agent: ureq::Agent,
+    min_interval: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl Client {
+    pub fn new(base_url: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            // A redirect means we're being sent to log in, which should be reported rather
+            // than followed.
+            .redirects(0)
+            .build();
+        Client {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            agent,
+            min_interval: MIN_INTERVAL,
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Override the delay between requests, so tests against a local server run quickly.
+    #[cfg(test)]
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    pub fn fetch_input(
+        &self,
+        session: &str,
+        year: usize,
+        day: usize,
+    ) -> Result<String, FetchError> {
+        self.get(session, &format!("/{}/day/{}/input", year, day))
+    }
+
+    /// Fetch the puzzle description for a day. The second part is only included once the first
+    /// has been solved.
+    pub fn fetch_puzzle(
+        &self,
+        session: &str,
+        year: usize,
+        day: usize,
+    ) -> Result<String, FetchError> {
+        self.get(session, &format!("/{}/day/{}", year, day))
+    }
+
+    /// Check that the server accepts the session token by requesting a page that is only
+    /// available when logged in.
+    pub fn check_session(&self, session: &str) -> Result<(), FetchError> {
+        self.get(session, "/settings").map(|_| ())
+    }
+
+    /// Submit `answer` for one part of a puzzle and report how the server judged it.
+    pub fn submit_answer(
+        &self,
+        session: &str,
+        year: usize,
+        day: usize,
+        part: usize,
+        answer: &str,
+    ) -> Result<Verdict, FetchError> {
+        let path = format!("/{}/day/{}/answer", year, day);
+        let level = part.to_string();
+        let body = self.post_form(session, &path, &[("level", &level), ("answer", answer)])?;
+        Ok(Verdict::parse(&body))
+    }
+
+    /// Fetch `path` using the given session token, returning the body of a successful
+    /// response.
+    pub fn get(&self, session: &str, path: &str) -> Result<String, FetchError> {
+        self.throttle();
+        let url = format!("{}{}", self.base_url, path);
+        let res = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", session.trim()))
+            .call();
+        read_response(res)
+    }
+
+    /// Post a form to `path` using the given session token, returning the body of a
+    /// successful response.
+    pub fn post_form(
+        &self,
+        session: &str,
+        path: &str,
+        fields: &[(&str, &str)],
+    ) -> Result<String, FetchError> {
+        self.throttle();
+        let url = format!("{}{}", self.base_url, path);
+        let res = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", session.trim()))
+            .send_form(fields);
+        read_response(res)
+    }
+
+    /// Wait until at least `min_interval` has passed since the previous request.
+    fn throttle(&self) {
+        let mut last = self.last_request.lock().unwrap();
+        if let Some(prev) = *last {
+            let elapsed = prev.elapsed();
+            if elapsed < self.min_interval {
+                std::thread::sleep(self.min_interval - elapsed);
+            }
+        }
+        *last = Some(Instant::now());
+    }
+}
+
+fn read_response(res: Result<ureq::Response, ureq::Error>) -> Result<String, FetchError> {
+    let res = match res {
+        Ok(res) => res,
+        Err(ureq::Error::Status(code, _)) => return Err(status_error(code)),
+        Err(ureq::Error::Transport(t)) => return Err(FetchError::Network(t.to_string())),
+    };
+    if res.status() != 200 {
+        return Err(status_error(res.status()));
+    }
+    let mut body = String::new();
+    if let Some(size) = res.header("Content-Length").and_then(|s| s.parse().ok()) {
+        body.reserve(size);
+    }
+    res.into_reader()
+        .read_to_string(&mut body)
+        .map_err(|e| FetchError::Network(e.to_string()))?;
+    if body.contains("Please log in") {
+        return Err(FetchError::SessionExpired);
+    }
+    Ok(body)
+}
+
+/// How the server judged a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    /// An answer was submitted too recently; try again after the given time.
+    Wait(Duration),
+    /// The part has already been solved, or the previous part hasn't been.
+    WrongLevel,
+    /// A response we don't recognize, with the text of the article.
+    Unknown(String),
+}
+
+impl Verdict {
+    fn parse(html: &str) -> Self {
+        // The verdict is the only <article> on the page.
+        let text = match html.find("<article>") {
+            Some(start) => {
+                let rest = &html[start..];
+                &rest[..rest.find("</article>").unwrap_or(rest.len())]
+            }
+            None => html,
+        };
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if text.contains("That's not the right answer") {
+            Verdict::Incorrect
+        } else if text.contains("You gave an answer too recently") {
+            Verdict::Wait(parse_wait(text).unwrap_or(Duration::from_secs(60)))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown(strip_tags(text))
+        }
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "incorrect: too high"),
+            Verdict::TooLow => write!(f, "incorrect: too low"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::Wait(d) => write!(f, "submitted too recently; wait {}s", d.as_secs()),
+            Verdict::WrongLevel => write!(f, "already solved, or not unlocked yet"),
+            Verdict::Unknown(text) => write!(f, "unrecognized response: {}", text),
+        }
+    }
+}
+
+/// Parse the remaining time out of e.g. "You have 4m 12s left to wait."
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+    let mut secs = 0;
+    for word in text[start..end].split_whitespace() {
+        let (num, unit) = word.split_at(word.find(|c: char| !c.is_ascii_digit())?);
+        let num = num.parse::<u64>().ok()?;
+        secs += match unit {
+            "h" => num * 3600,
+            "m" => num * 60,
+            "s" => num,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn status_error(code: u16) -> FetchError {
+    match code {
+        404 => FetchError::NotUnlocked,
+        // A missing or malformed session gets a 400 or 500, and an expired one is redirected
+        // to the login page.
+        400 | 500 | 301 | 302 | 303 | 307 => FetchError::SessionExpired,
+        _ => FetchError::Status(code),
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use std::io::BufRead;
+    use std::io::BufReader;
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    /// A request received by the mock server.
+    #[derive(Debug, Clone)]
+    pub struct Request {
+        pub method: String,
+        pub path: String,
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+    }
+
+    impl Request {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+        }
+    }
+
+    /// A local HTTP server that answers every request with the response chosen by `respond`
+    /// and records the requests it saw.
+    pub struct Server {
+        pub url: String,
+        pub requests: Arc<Mutex<Vec<Request>>>,
+    }
+
+    impl Server {
+        pub fn start<F>(respond: F) -> Self
+        where
+            F: Fn(&Request) -> (u16, Vec<(&'static str, String)>, String) + Send + 'static,
+        {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let recorded = requests.clone();
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let mut stream = match stream {
+                        Ok(s) => s,
+                        Err(_) => return,
+                    };
+                    let req = match read_request(&mut BufReader::new(&stream)) {
+                        Some(r) => r,
+                        None => continue,
+                    };
+                    let (status, headers, body) = respond(&req);
+                    recorded.lock().unwrap().push(req);
+                    let mut res = format!("HTTP/1.1 {} Mock\r\n", status);
+                    for (k, v) in headers {
+                        res.push_str(&format!("{}: {}\r\n", k, v));
+                    }
+                    res.push_str(&format!(
+                        "Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    ));
+                    let _ = stream.write_all(res.as_bytes());
+                }
+            });
+            Server { url, requests }
+        }
+    }
+
+    fn read_request<R: BufRead>(reader: &mut R) -> Option<Request> {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let mut parts = line.split_whitespace();
+        let method = parts.next()?.to_owned();
+        let path = parts.next()?.to_owned();
+        let mut headers = Vec::new();
+        loop {
+            line.clear();
+            reader.read_line(&mut line).ok()?;
+            let trimmed = line.trim_end();
+            if trimmed.is_empty() {
+                break;
+            }
+            let (k, v) = trimmed.split_once(':')?;
+            headers.push((k.trim().to_owned(), v.trim().to_owned()));
+        }
+        let len = headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, v)| v.parse::<usize>().ok())
+            .unwrap_or(0);
+        let mut body = vec![0; len];
+        reader.read_exact(&mut body).ok()?;
+        Some(Request {
+            method,
+            path,
+            headers,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::mock::Server;
+    use super::*;
+
+    fn client(url: &str, min_interval: Duration) -> Client {
+        Client::new(url).with_min_interval(min_interval)
+    }
+
+    #[test]
+    fn fetch_input() {
+        let server = Server::start(|_| (200, vec![], "1\n2\n3\n".to_owned()));
+        let body = client(&server.url, Duration::ZERO)
+            .fetch_input("abc\n", 2021, 1)
+            .unwrap();
+        assert_eq!(body, "1\n2\n3\n");
+
+        let requests = server.requests.lock().unwrap();
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2021/day/1/input");
+        assert!(requests[0].body.is_empty());
+        assert_eq!(requests[0].header("cookie"), Some("session=abc"));
+        assert_eq!(requests[0].header("user-agent"), Some(USER_AGENT));
+    }
+
+    #[test]
+    fn status_errors() {
+        let server = Server::start(|req| match req.path.as_str() {
+            "/2021/day/25/input" => (404, vec![], "Not Found".to_owned()),
+            "/2021/day/1/input" => (
+                400,
+                vec![],
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.".to_owned(),
+            ),
+            "/2021/day/2/input" => (500, vec![], "Internal Server Error".to_owned()),
+            "/2021/day/3/input" => (
+                302,
+                vec![("Location", "/auth/login".to_owned())],
+                String::new(),
+            ),
+            "/2021/day/4/input" => (200, vec![], "Please log in".to_owned()),
+            _ => (418, vec![], String::new()),
+        });
+        let client = client(&server.url, Duration::ZERO);
+        assert_eq!(
+            client.fetch_input("", 2021, 25),
+            Err(FetchError::NotUnlocked)
+        );
+        for day in 1..=4 {
+            assert_eq!(
+                client.fetch_input("", 2021, day),
+                Err(FetchError::SessionExpired)
+            );
+        }
+        assert_eq!(
+            client.fetch_input("", 2021, 5),
+            Err(FetchError::Status(418))
+        );
+    }
+
+    #[test]
+    fn network_error() {
+        // Nothing is listening on a port we just released.
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let client = client(&format!("http://{}", addr), Duration::ZERO);
+        assert!(matches!(
+            client.fetch_input("", 2021, 1),
+            Err(FetchError::Network(_))
+        ));
+    }
+
+    #[test]
+    fn submit_answer() {
+        let server = Server::start(|req| {
+            let article = match req.body.as_str() {
+                "level=1&answer=42" => {
+                    "<p>That's the right answer!  You are one gold star closer.</p>"
+                }
+                "level=1&answer=100" => {
+                    "<p>That's not the right answer; your answer is too high.</p>"
+                }
+                "level=1&answer=1" => "<p>That's not the right answer; your answer is too low.</p>",
+                "level=2&answer=7" => "<p>That's not the right answer.  If you're stuck...</p>",
+                "level=2&answer=8" => {
+                    "<p>You gave an answer too recently; you have to wait after submitting an \
+                     answer before trying again.  You have 4m 12s left to wait.</p>"
+                }
+                "level=2&answer=9" => {
+                    "<p>You don't seem to be solving the right level.  Did you already complete \
+                     it?</p>"
+                }
+                _ => "<p>Something <em>else</em>.</p>",
+            };
+            (
+                200,
+                vec![],
+                format!("<main>\n<article>{}</article>\n</main>", article),
+            )
+        });
+        let client = client(&server.url, Duration::ZERO);
+        let submit = |part, answer| client.submit_answer("", 2021, 3, part, answer).unwrap();
+        assert_eq!(submit(1, "42"), Verdict::Correct);
+        assert_eq!(submit(1, "100"), Verdict::TooHigh);
+        assert_eq!(submit(1, "1"), Verdict::TooLow);
+        assert_eq!(submit(2, "7"), Verdict::Incorrect);
+        assert_eq!(submit(2, "8"), Verdict::Wait(Duration::from_secs(252)));
+        assert_eq!(submit(2, "9"), Verdict::WrongLevel);
+        assert_eq!(
+            submit(2, "10"),
+            Verdict::Unknown("Something else.".to_owned())
+        );
+
+        let requests = server.requests.lock().unwrap();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2021/day/3/answer");
+        assert_eq!(
+            requests[0].header("content-type"),
+            Some("application/x-www-form-urlencoded")
+        );
+    }
+
+    #[test]
+    fn throttle() {
+        let server = Server::start(|_| (200, vec![], "ok".to_owned()));
+        let client = client(&server.url, Duration::from_millis(100));
+        let clock = Instant::now();
+        for _ in 0..3 {
+            client.get("", "/").unwrap();
+        }
+        assert!(clock.elapsed() >= Duration::from_millis(200));
+    }
+}
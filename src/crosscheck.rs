@@ -0,0 +1,97 @@
+//! Run the Python reference implementations in `python/` to compare with our answers.
+//!
+//! Only scripts that print each part's answer on its own line are listed. `24.py` checks a
+//! hard-coded model number rather than solving its input, and `permute.py` is a helper with
+//! no entry point.
+
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+use std::process::Stdio;
+
+use crate::prelude::*;
+
+/// The year, day and script name of each reference script.
+const SCRIPTS: &[(usize, usize, &str)] = &[
+    (2021, 8, "day8.py"),
+    (2021, 8, "day8-brute.py"),
+    (2021, 11, "day11.py"),
+];
+
+/// The days of `year` that have at least one reference script.
+pub fn days(year: usize) -> Vec<usize> {
+    let mut days = SCRIPTS
+        .iter()
+        .filter(|&&(y, _, _)| y == year)
+        .map(|&(_, d, _)| d)
+        .collect::<Vec<_>>();
+    days.dedup();
+    days
+}
+
+pub fn scripts(year: usize, day: usize) -> Vec<&'static str> {
+    SCRIPTS
+        .iter()
+        .filter(|&&(y, d, _)| y == year && d == day)
+        .map(|&(_, _, s)| s)
+        .collect()
+}
+
+/// Run `script` with `python` on `input`, returning the answer to each part in order.
+pub fn run_script(python: &str, script: &Path, input: &str) -> Result<Vec<usize>> {
+    let mut child = Command::new(python)
+        .arg(script)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("run {} {}", python, script.display()))?;
+    // The scripts read all of stdin before printing anything, so this can't deadlock.
+    child
+        .stdin
+        .take()
+        .expect("piped stdin")
+        .write_all(input.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "{} failed ({}): {}",
+            script.display(),
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    parse_answers(&String::from_utf8_lossy(&output.stdout))
+        .with_context(|| format!("read output of {}", script.display()))
+}
+
+fn parse_answers(output: &str) -> Result<Vec<usize>> {
+    output
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            l.trim()
+                .parse()
+                .map_err(|_| anyhow!("expected an answer, got '{}'", l))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn answers() {
+        assert_eq!(parse_answers("26\n61229\n").unwrap(), vec![26, 61229]);
+        assert!(parse_answers("26\nmapping: {}\n").is_err());
+    }
+
+    #[test]
+    fn scripts_by_day() {
+        assert_eq!(days(2021), vec![8, 11]);
+        assert!(days(2020).is_empty());
+        assert_eq!(scripts(2021, 8), vec!["day8.py", "day8-brute.py"]);
+        assert!(scripts(2021, 24).is_empty());
+    }
+}
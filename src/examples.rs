@@ -0,0 +1,94 @@
+//! Extract example inputs from a puzzle description.
+
+/// The contents of every `<pre><code>` block in the page, with markup removed.
+pub fn extract(html: &str) -> Vec<String> {
+    const OPEN: &str = "<pre><code>";
+    const CLOSE: &str = "</code></pre>";
+
+    let mut blocks = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find(OPEN) {
+        rest = &rest[start + OPEN.len()..];
+        let end = match rest.find(CLOSE) {
+            Some(end) => end,
+            None => break,
+        };
+        blocks.push(unescape(&strip_tags(&rest[..end])));
+        rest = &rest[end + CLOSE.len()..];
+    }
+    blocks
+}
+
+/// Remove tags such as the `<em>` used to highlight parts of an example.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn unescape(text: &str) -> String {
+    let entities = [
+        ("&lt;", "<"),
+        ("&gt;", ">"),
+        ("&quot;", "\""),
+        ("&#39;", "'"),
+        ("&apos;", "'"),
+        ("&amp;", "&"),
+    ];
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    'outer: while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        for (entity, c) in entities {
+            if let Some(tail) = rest.strip_prefix(entity) {
+                out.push_str(c);
+                rest = tail;
+                continue 'outer;
+            }
+        }
+        out.push('&');
+        rest = &rest[1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn extract_blocks() {
+        let html = "\
+<article><p>For example:</p>
+<pre><code>199
+200
+<em>208</em>
+</code></pre>
+<p>Chunks like <code>[&lt;&gt;]</code> are inline.</p>
+<pre><code>[({(&lt;(())[]&gt;[[{[]{&lt;()&lt;&gt;&gt;
+a &amp;&amp; b &amp;lt;
+</code></pre></article>";
+        assert_eq!(
+            extract(html),
+            vec![
+                "199\n200\n208\n".to_owned(),
+                "[({(<(())[]>[[{[]{<()<>>\na && b &lt;\n".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn unterminated_block() {
+        assert!(extract("<pre><code>1\n2\n").is_empty());
+    }
+}
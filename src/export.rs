@@ -0,0 +1,46 @@
+//! Export the structures a day's solution reconstructs, for inspection in other tools.
+
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use anyhow::Result;
+
+use crate::params::Params;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+    /// An ASCII PLY point cloud, which most 3D viewers can open.
+    Ply,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            "ply" => Ok(Format::Ply),
+            _ => Err(anyhow!("unknown format {:?}; expected csv, json or ply", s)),
+        }
+    }
+}
+
+/// Renders what a day reconstructs from its input in a chosen format.
+pub struct Exporter {
+    pub what: &'static str,
+    pub export: fn(&str, Format, &Params) -> Result<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_format() {
+        assert_eq!("ply".parse::<Format>().unwrap(), Format::Ply);
+        assert!("obj".parse::<Format>().is_err());
+    }
+}
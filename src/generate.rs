@@ -0,0 +1,107 @@
+//! Random puzzle inputs for stress and scaling tests.
+
+use anyhow::anyhow;
+use anyhow::Result;
+
+/// A small, seedable random number generator (SplitMix64), so a generated input can be
+/// reproduced from its seed.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "empty range");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A number in `lo..=hi`.
+    pub fn range(&mut self, lo: isize, hi: isize) -> isize {
+        lo + self.below((hi - lo + 1) as usize) as isize
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Produces random inputs for a day. What `size` measures depends on the day.
+pub struct Generator {
+    pub size: &'static str,
+    pub default_size: usize,
+    /// The largest input the day's solution can handle, if it has a limit.
+    pub max_size: Option<usize>,
+    pub generate: fn(&mut Rng, usize) -> String,
+}
+
+impl Generator {
+    /// A random input of `size` from `seed`.
+    pub fn input(&self, seed: u64, size: usize) -> Result<String> {
+        match self.max_size {
+            Some(max) if size > max => Err(anyhow!(
+                "invalid size {}: the {} can be at most {}",
+                size,
+                self.size,
+                max
+            )),
+            _ => Ok((self.generate)(&mut Rng::new(seed), size)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reproducible() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+        assert_eq!(
+            (0..10).map(|_| a.next_u64()).collect::<Vec<_>>(),
+            (0..10).map(|_| b.next_u64()).collect::<Vec<_>>()
+        );
+        for day in crate::registry::days(2021) {
+            let Some(ref g) = day.generator else {
+                continue;
+            };
+            let size = g.default_size.min(5);
+            assert_eq!(
+                (g.generate)(&mut Rng::new(1), size),
+                (g.generate)(&mut Rng::new(1), size),
+                "day {}",
+                day.day
+            );
+        }
+    }
+
+    #[test]
+    fn size_limit() {
+        let day = crate::registry::find(2021, 12).unwrap();
+        let generator = day.generator.as_ref().unwrap();
+        assert!(generator.input(1, 30).is_ok());
+        assert!(generator.input(1, 31).is_err());
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = Rng::new(3);
+        for _ in 0..1000 {
+            assert!((-5..=5).contains(&rng.range(-5, 5)));
+            assert!(rng.below(3) < 3);
+        }
+    }
+}
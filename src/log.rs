@@ -0,0 +1,91 @@
+use std::cell::RefCell;
+use std::fmt::Display;
+
+/// The importance of a diagnostic message. Messages are written when the runner's verbosity
+/// is at least their level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Output that was explicitly asked for, e.g. by `--explain`.
+    Info,
+    /// Visualizations of intermediate state, enabled with `-v`.
+    Debug,
+    /// Step-by-step detail, enabled with `-vv`.
+    Trace,
+}
+
+thread_local! {
+    static CAPTURE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Run `f`, returning everything logged on this thread while it ran instead of printing it.
+/// Lets concurrent runs print their diagnostics alongside their own results.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, String) {
+    let outer = CAPTURE.with(|c| c.borrow_mut().replace(String::new()));
+    let result = f();
+    let captured = CAPTURE.with(|c| std::mem::replace(&mut *c.borrow_mut(), outer));
+    (result, captured.unwrap_or_default())
+}
+
+/// The sink solvers write diagnostics to instead of printing directly, so that the default
+/// output of a run is just the answers.
+#[derive(Debug, Clone, Copy)]
+pub struct Log {
+    max: Level,
+}
+
+impl Log {
+    pub fn new(verbosity: usize) -> Self {
+        let max = match verbosity {
+            0 => Level::Info,
+            1 => Level::Debug,
+            _ => Level::Trace,
+        };
+        Log { max }
+    }
+
+    pub fn enabled(&self, level: Level) -> bool {
+        level <= self.max
+    }
+
+    pub fn log<D: Display>(&self, level: Level, msg: D) {
+        if self.enabled(level) {
+            CAPTURE.with(|c| match *c.borrow_mut() {
+                Some(ref mut buf) => {
+                    use std::fmt::Write;
+                    writeln!(buf, "{}", msg).unwrap();
+                }
+                None => println!("{}", msg),
+            });
+        }
+    }
+
+    pub fn info<D: Display>(&self, msg: D) {
+        self.log(Level::Info, msg)
+    }
+
+    pub fn debug<D: Display>(&self, msg: D) {
+        self.log(Level::Debug, msg)
+    }
+
+    pub fn trace<D: Display>(&self, msg: D) {
+        self.log(Level::Trace, msg)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn capture_collects_enabled_messages() {
+        let log = Log::new(1);
+        let (answer, captured) = capture(|| {
+            log.info("shown");
+            log.debug(format_args!("{} too", "shown"));
+            log.trace("hidden");
+            42
+        });
+        assert_eq!(answer, 42);
+        assert_eq!(captured, "shown\nshown too\n");
+    }
+}
@@ -1,37 +1,38 @@
+use std::cell::RefCell;
 use std::io::Read;
 use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
 
 use anyhow::anyhow;
 use anyhow::Context;
 use anyhow::Result;
+use once_cell::sync::OnceCell;
 
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day2;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
+use crate::answers::Answers;
+use crate::cache::Cache;
+use crate::client::Client;
+use crate::client::Verdict;
+use crate::log::Log;
+use crate::params::Params;
+use crate::solution::Answer;
+use crate::solution::AnySolution;
+
+mod answers;
+mod cache;
+mod client;
+mod crosscheck;
+mod examples;
+mod export;
+mod generate;
 mod iter;
+mod log;
+mod params;
+mod registry;
 mod search;
+mod session;
+mod solution;
+mod y2021;
 
 mod prelude {
     use std::str::FromStr;
@@ -44,6 +45,8 @@ mod prelude {
     pub use fxhash::FxHashMap as HashMap;
     pub use fxhash::FxHashSet as HashSet;
 
+    pub use crate::registry::Day;
+    pub use crate::solution::Solution;
     pub use crate::Runner;
 
     pub fn parse_split<T>(nums: &str, delim: char) -> Result<Vec<T>, <T as FromStr>::Err>
@@ -58,69 +61,266 @@ mod prelude {
 }
 
 const CACHE_DIR: &str = "/Users/cwbriones/.advent-of-code";
-const YEAR: usize = 21;
-
-fn cached<F>(key: &str, f: F) -> Result<String>
-where
-    F: Fn() -> Result<String>,
-{
-    let full_path = Path::new(CACHE_DIR).join(key);
-    let dir = full_path.parent().expect("non-empty path");
-
-    if full_path.exists() {
-        return std::fs::read_to_string(full_path).map_err(Into::into);
-    }
-    std::fs::create_dir_all(dir)?;
-    let content =
-        f().with_context(|| format!("populate cache entry for {}", full_path.display()))?;
-    std::fs::write(full_path, &content)?;
-    Ok(content)
-}
-
-fn fetch_input(day: usize) -> Result<String> {
-    let token = get_session_token()?;
-    let url = format!("https://adventofcode.com/20{}/day/{}/input", YEAR, day);
-    let res = ureq::get(&url)
-        .set("Cookie", &format!("session={}", token.trim()))
-        .call()?;
-    if res.status() != 200 {
-        return Err(anyhow!("unexpected status code: {}", res.status()));
-    }
-    let mut body = String::new();
-    if let Some(size) = res.header("Content-Length") {
-        let size = size.parse::<usize>().context("parse content-length")?;
-        body.reserve(size);
-    }
-    res.into_reader()
-        .read_to_string(&mut body)
-        .context("read response body")?;
-    Ok(body)
-}
-
-fn get_session_token() -> Result<String> {
-    if let Ok(val) = std::env::var("AOC_TOKEN") {
-        return Ok(val);
-    }
-    let full_path = Path::new(CACHE_DIR).join("session");
-    if !full_path.exists() {
-        return Err(anyhow!(
-            "session token not found at '{}'",
-            full_path.display()
-        ));
+fn input_key(year: usize, day: usize) -> String {
+    format!("input/{}/{}", year, day)
+}
+
+/// Read the input for `day` from the cache, fetching it on a miss unless running offline.
+fn load_input(opts: &Options, day: usize) -> Result<String> {
+    let key = input_key(opts.year, day);
+    if opts.offline {
+        return opts
+            .cache
+            .get(&key)?
+            .ok_or_else(|| anyhow!("input for day {} not cached", day));
+    }
+    opts.cache
+        .get_or_insert_with(&key, || fetch_input(opts, day))
+}
+
+/// Describe what is wrong with a cached input, if it doesn't look like a puzzle input.
+fn validate_input(content: &str) -> Option<&'static str> {
+    let trimmed = content.trim();
+    if trimmed.is_empty() {
+        Some("empty")
+    } else if trimmed.starts_with('<')
+        || trimmed.contains("Please log in")
+        || trimmed.contains("Please don't repeatedly request")
+    {
+        Some("looks like an error page")
+    } else {
+        None
+    }
+}
+
+fn inputs_dir_path(dir: &Path, day: usize) -> PathBuf {
+    dir.join(format!("{}.txt", day))
+}
+
+/// The input for `day` from `--inputs-dir` if one was given, otherwise from the cache.
+fn day_input(opts: &Options, day: usize) -> Result<String> {
+    match opts.inputs_dir {
+        Some(ref dir) => {
+            let path = inputs_dir_path(dir, day);
+            std::fs::read_to_string(&path).with_context(|| format!("read input {}", path.display()))
+        }
+        None => load_input(opts, day),
     }
-    std::fs::read_to_string(full_path).map_err(Into::into)
+}
+
+fn puzzle_key(year: usize, day: usize) -> String {
+    format!("puzzle/{}/{}", year, day)
+}
+
+/// Read the puzzle description for `day` from the cache, fetching it on a miss unless running
+/// offline.
+fn load_puzzle(opts: &Options, day: usize) -> Result<String> {
+    let key = puzzle_key(opts.year, day);
+    if opts.offline {
+        return opts
+            .cache
+            .get(&key)?
+            .ok_or_else(|| anyhow!("puzzle for day {} not cached", day));
+    }
+    opts.cache.get_or_insert_with(&key, || {
+        let token = session_token(opts)?;
+        opts.client
+            .fetch_puzzle(&token, opts.year, day)
+            .with_context(|| format!("fetch puzzle for day {}", day))
+    })
+}
+
+fn fetch_input(opts: &Options, day: usize) -> Result<String> {
+    let token = session_token(opts)?;
+    opts.client
+        .fetch_input(&token, opts.year, day)
+        .with_context(|| format!("fetch input for day {}", day))
+}
+
+/// The session token, checked against the server the first time it is needed so an expired
+/// token is reported once instead of failing every fetch.
+fn session_token(opts: &Options) -> Result<String> {
+    let checked = opts.session.get_or_init(|| {
+        let token = session::load(&opts.cache).map_err(|e| format!("{:#}", e))?;
+        session::check(&opts.client, &token).map_err(|e| format!("{:#}", e))?;
+        Ok(token)
+    });
+    checked.clone().map_err(|e| anyhow!(e))
 }
 
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
 struct Args {
+    /// The year to run, fetch or submit for. Defaults to the latest year with solutions.
+    #[structopt(short, long)]
+    year: Option<usize>,
     #[structopt(short, long)]
     day: Option<usize>,
     #[structopt(short, long)]
     part: Option<usize>,
-    #[structopt(short, long)]
-    input: Option<String>,
+    /// List the days with solutions.
+    #[structopt(long)]
+    list: bool,
+    /// Read the input from this file, or `-` for stdin, instead of the cache. Repeat to run
+    /// the day against several inputs.
+    #[structopt(short, long, number_of_values = 1)]
+    input: Vec<String>,
+    /// Read each day's input from `DAY.txt` in this directory instead of the cache. A full run
+    /// only runs the days that have an input.
+    #[structopt(long, parse(from_os_str), conflicts_with = "input")]
+    inputs_dir: Option<PathBuf>,
+    /// Print a step-by-step explanation of the solution, for days that support it.
+    #[structopt(long)]
+    explain: bool,
+    /// Print diagnostics from the solvers. Repeat for more detail.
+    #[structopt(short, long, parse(from_occurrences))]
+    verbose: usize,
+    /// Number of days to run concurrently when running every day.
+    #[structopt(short, long, default_value = "1")]
+    jobs: usize,
+    /// Number of slowest parts to list in the summary after running every day.
+    #[structopt(long, default_value = "5")]
+    slowest: usize,
+    /// Run every solution of days that have several, and check that they agree.
+    #[structopt(long)]
+    variants: bool,
+    /// Run the solution with this name instead of the default, for days that have one.
+    #[structopt(long, conflicts_with = "variants")]
+    solution: Option<String>,
+    /// Set one of the day's parameters, such as `min-overlap=12` for day 19. Repeat to set
+    /// several.
+    #[structopt(short = "P", long = "param", number_of_values = 1)]
+    params: Vec<params::Param>,
+    /// Fail instead of fetching inputs that are not cached.
+    #[structopt(long)]
+    offline: bool,
+    /// Directory for cached inputs, known answers and the session token.
+    #[structopt(long, env = "AOC_CACHE_DIR", default_value = CACHE_DIR, parse(from_os_str))]
+    cache_dir: PathBuf,
+    /// The AoC server to talk to, e.g. a local stub for testing.
+    #[structopt(long, env = "AOC_BASE_URL", default_value = client::BASE_URL, hidden = true)]
+    base_url: String,
+    #[structopt(subcommand)]
+    cmd: Option<Command>,
+}
+
+#[derive(StructOpt)]
+enum Command {
+    /// Manage cached puzzle inputs.
+    Cache(CacheCommand),
+    /// Manage the AoC session token.
+    Session(SessionCommand),
+    /// Compute the answer for one part of a day and submit it.
+    Submit {
+        day: usize,
+        part: usize,
+        /// Submit this answer instead of computing one.
+        #[structopt(long)]
+        answer: Option<String>,
+    },
+    /// Compare the answers for a day with those of its Python reference script, or for every
+    /// day with one if no day is given.
+    Crosscheck {
+        day: Option<usize>,
+        /// The Python interpreter to run the scripts with.
+        #[structopt(long, default_value = "python3")]
+        python: String,
+        /// Directory containing the reference scripts.
+        #[structopt(long, default_value = "python", parse(from_os_str))]
+        scripts_dir: PathBuf,
+    },
+    /// Time every solution of a day on a generated input, keeping each part's best time over
+    /// several runs. The same day, size and seed always give the same input.
+    Bench {
+        day: usize,
+        /// How big an input to generate; what this counts depends on the day.
+        #[structopt(long)]
+        size: Option<usize>,
+        /// Seed for the generator.
+        #[structopt(long, default_value = "1")]
+        seed: u64,
+        /// Number of times to run each solution.
+        #[structopt(long, default_value = "5")]
+        runs: usize,
+    },
+    /// Print a random input for a day, for stress and scaling tests.
+    Generate {
+        day: usize,
+        /// How big an input to generate; what this counts depends on the day.
+        #[structopt(long)]
+        size: Option<usize>,
+        /// Seed for the generator. A random seed is chosen and reported if not given.
+        #[structopt(long)]
+        seed: Option<u64>,
+    },
+    /// Write out what a day's solution reconstructs from its input, such as the day 19 beacon
+    /// map, for viewing in other tools or keeping as a test fixture.
+    Export {
+        day: usize,
+        /// One of `csv`, `json` or `ply`.
+        #[structopt(long, default_value = "json")]
+        format: export::Format,
+        /// File to write to instead of stdout.
+        #[structopt(short, long, parse(from_os_str))]
+        out: Option<PathBuf>,
+    },
+    /// Save the example blocks from a day's puzzle description as candidate test inputs.
+    Examples {
+        day: usize,
+        /// Directory to write `example-YEAR-DAY-N.in` files to.
+        #[structopt(long, default_value = "tests", parse(from_os_str))]
+        out_dir: PathBuf,
+        /// Fetch the puzzle again, e.g. to pick up the second part once the first is solved.
+        #[structopt(long)]
+        refresh: bool,
+        /// Overwrite existing example files.
+        #[structopt(long)]
+        force: bool,
+    },
+}
+
+#[derive(StructOpt)]
+enum SessionCommand {
+    /// Check that the session token is accepted by the AoC servers.
+    Check,
+    /// Store a session token in the cache directory, readable only by the current user. The
+    /// token is read from stdin if not given.
+    Set { token: Option<String> },
+}
+
+#[derive(StructOpt)]
+enum CacheCommand {
+    /// List the cached inputs, flagging any that don't look like puzzle inputs.
+    List,
+    /// Fetch and cache the inputs for the days from FIRST to LAST.
+    Prefetch {
+        first: usize,
+        last: Option<usize>,
+        /// Fetch again even if the input is already cached.
+        #[structopt(long)]
+        force: bool,
+    },
+    /// Remove the cached input for a day.
+    Invalidate { day: usize },
+}
+
+/// Settings shared by every day in a run.
+#[derive(Debug)]
+struct Options {
+    year: usize,
+    part: Option<usize>,
+    explain: bool,
+    log: Log,
+    variants: bool,
+    solution: Option<String>,
+    params: Params,
+    offline: bool,
+    inputs_dir: Option<PathBuf>,
+    cache: Cache,
+    client: Client,
+    /// The session token once it has been loaded and checked, or why that failed.
+    session: OnceCell<std::result::Result<String, String>>,
 }
 
 fn read_input(path: &str) -> Result<String> {
@@ -135,114 +335,846 @@ fn read_input(path: &str) -> Result<String> {
 
 fn main() -> Result<()> {
     let args = Args::from_args();
+    if args.list {
+        for year in registry::years() {
+            for d in registry::days(year) {
+                println!("{} {:>2}  {}", d.year, d.day, d.title);
+            }
+        }
+        return Ok(());
+    }
     if let Some(p) = args.part {
         if p != 1 && p != 2 {
             return Err(anyhow!("invalid value for part: {}", p));
         }
     }
-    let input = if let Some(ref path) = args.input {
-        Some(read_input(path)?)
-    } else {
-        None
+    if args.jobs == 0 {
+        return Err(anyhow!("invalid value for jobs: {}", args.jobs));
+    }
+    let mut inputs = args
+        .input
+        .iter()
+        .map(|path| read_input(path).with_context(|| format!("read input {}", path)))
+        .collect::<Result<Vec<_>>>()?;
+    // Submitting only needs the answer for the part being submitted.
+    let part = match args.cmd {
+        Some(Command::Submit { part, .. }) => Some(part),
+        _ => args.part,
+    };
+    // Benchmarks compare every solution.
+    let variants = args.variants || matches!(args.cmd, Some(Command::Bench { .. }));
+    let year = match args.year {
+        Some(y) => y,
+        None => *registry::years()
+            .last()
+            .ok_or_else(|| anyhow!("no years registered"))?,
+    };
+    let opts = Options {
+        year,
+        part,
+        explain: args.explain,
+        log: Log::new(args.verbose),
+        variants,
+        solution: args.solution,
+        params: Params::new(args.params),
+        offline: args.offline,
+        inputs_dir: args.inputs_dir,
+        cache: Cache::new(&args.cache_dir),
+        client: Client::new(&args.base_url),
+        session: OnceCell::new(),
     };
-    return match args.day {
-        Some(d) if (1..=25).contains(&d) => dispatch(d, args.part, input),
+    match args.cmd {
+        Some(Command::Cache(cmd)) => return run_cache_command(cmd, &opts),
+        Some(Command::Session(cmd)) => return run_session_command(cmd, &opts),
+        Some(Command::Submit { day, part, answer }) => {
+            if inputs.len() > 1 {
+                return Err(anyhow!("only one input can be used when submitting"));
+            }
+            return submit(&opts, day, part, answer, inputs.pop());
+        }
+        Some(Command::Crosscheck {
+            day,
+            python,
+            scripts_dir,
+        }) => {
+            if inputs.len() > 1 {
+                return Err(anyhow!("only one input can be cross-checked at a time"));
+            }
+            return run_crosscheck(&opts, day, &python, &scripts_dir, inputs.pop());
+        }
+        Some(Command::Bench {
+            day,
+            size,
+            seed,
+            runs,
+        }) => return bench(&opts, day, size, seed, runs),
+        Some(Command::Generate { day, size, seed }) => {
+            return generate_input(&opts, day, size, seed)
+        }
+        Some(Command::Export { day, format, out }) => {
+            if inputs.len() > 1 {
+                return Err(anyhow!("only one input can be exported at a time"));
+            }
+            return export_day(&opts, day, format, out.as_deref(), inputs.pop());
+        }
+        Some(Command::Examples {
+            day,
+            out_dir,
+            refresh,
+            force,
+        }) => return save_examples(&opts, day, &out_dir, refresh, force),
+        None => {}
+    }
+    match args.day {
+        Some(d) if (1..=25).contains(&d) && inputs.len() > 1 => {
+            run_inputs(d, &opts, &args.input, inputs)
+        }
+        Some(d) if (1..=25).contains(&d) => {
+            let results = dispatch(d, &opts, inputs.pop())?;
+            results.iter().for_each(print_result);
+            check_failures(&results)
+        }
         Some(d) => Err(anyhow!("invalid value for day: {}", d)),
-        None => (1..25)
-            .map(|d| dispatch(d, args.part, None))
-            .collect::<Result<Vec<()>>>()
-            .map(|_| ()),
+        None if !inputs.is_empty() => Err(anyhow!("--input requires --day")),
+        None if opts.solution.is_some() => Err(anyhow!("--solution requires --day")),
+        None if !opts.params.is_empty() => Err(anyhow!("--param requires --day")),
+        None => run_all(&opts, args.jobs, args.slowest),
+    }
+}
+
+fn run_cache_command(cmd: CacheCommand, opts: &Options) -> Result<()> {
+    let cache = &opts.cache;
+    match cmd {
+        CacheCommand::List => {
+            let mut days = cache
+                .list(&format!("input/{}", opts.year))?
+                .into_iter()
+                .filter_map(|name| name.parse::<usize>().ok())
+                .collect::<Vec<_>>();
+            days.sort_unstable();
+            for day in days {
+                let content = cache.get(&input_key(opts.year, day))?.unwrap_or_default();
+                println!(
+                    "{:>3}  {:>8} bytes  {:>6} lines  {}",
+                    day,
+                    content.len(),
+                    content.lines().count(),
+                    validate_input(&content).unwrap_or("ok")
+                );
+            }
+        }
+        CacheCommand::Prefetch { first, last, force } => {
+            let last = last.unwrap_or(first);
+            if first == 0 || first > last || last > 25 {
+                return Err(anyhow!("invalid range of days: {}..={}", first, last));
+            }
+            if opts.offline {
+                return Err(anyhow!("cannot prefetch inputs in offline mode"));
+            }
+            for day in first..=last {
+                if force {
+                    // Fetch before replacing, so a failed fetch keeps the cached input.
+                    cache.insert(&input_key(opts.year, day), &fetch_input(opts, day)?)?;
+                } else {
+                    load_input(opts, day)?;
+                }
+                println!("cached input for day {}", day);
+            }
+        }
+        CacheCommand::Invalidate { day } => {
+            if cache.remove(&input_key(opts.year, day))? {
+                println!("removed input for day {}", day);
+            } else {
+                println!("input for day {} not cached", day);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_session_command(cmd: SessionCommand, opts: &Options) -> Result<()> {
+    match cmd {
+        SessionCommand::Check => {
+            let token = session::load(&opts.cache)?;
+            session::check(&opts.client, &token)?;
+            println!("session token is valid");
+        }
+        SessionCommand::Set { token } => {
+            let token = match token {
+                Some(t) => t,
+                None => read_input("-")?,
+            };
+            session::store(&opts.cache, &token)?;
+            println!(
+                "stored session token in {}",
+                opts.cache.path("session").display()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn submit(
+    opts: &Options,
+    day: usize,
+    part: usize,
+    answer: Option<String>,
+    input: Option<String>,
+) -> Result<()> {
+    if !(1..=25).contains(&day) {
+        return Err(anyhow!("invalid value for day: {}", day));
+    }
+    if part != 1 && part != 2 {
+        return Err(anyhow!("invalid value for part: {}", part));
+    }
+    let answer = match answer {
+        Some(a) => a,
+        None => {
+            let results = dispatch(day, opts, input)?;
+            results.iter().for_each(print_result);
+            let result = results
+                .first()
+                .ok_or_else(|| anyhow!("no answer for day {}, part {}", day, part))?;
+            match result.answer {
+                Ok(a) => a.to_string(),
+                Err(ref e) => return Err(anyhow!("{} failed: {}", result.label(), e)),
+            }
+        }
+    };
+
+    let answers_path = opts.cache.path(&answers_key(opts.year));
+    let mut answers = Answers::load(&answers_path)?;
+    match answers.get(day, part) {
+        Some(known) if known == answer => {
+            println!("{} is already recorded as the correct answer", answer);
+            return Ok(());
+        }
+        Some(known) => {
+            return Err(anyhow!(
+                "not submitting {}; the correct answer is recorded as {}",
+                answer,
+                known
+            ))
+        }
+        None => {}
+    }
+
+    let token = session_token(opts)?;
+    let verdict = opts
+        .client
+        .submit_answer(&token, opts.year, day, part, &answer)
+        .with_context(|| format!("submit answer for day {}, part {}", day, part))?;
+    println!("Day {}, Part {}: {} is {}", day, part, answer, verdict);
+    if verdict == Verdict::Correct {
+        answers.insert(day, part, &answer);
+        answers.save(&answers_path)?;
+    }
+    Ok(())
+}
+
+fn save_examples(
+    opts: &Options,
+    day: usize,
+    out_dir: &Path,
+    refresh: bool,
+    force: bool,
+) -> Result<()> {
+    if !(1..=25).contains(&day) {
+        return Err(anyhow!("invalid value for day: {}", day));
+    }
+    if refresh {
+        if opts.offline {
+            return Err(anyhow!("cannot refresh the puzzle in offline mode"));
+        }
+        opts.cache.remove(&puzzle_key(opts.year, day))?;
+    }
+    let blocks = examples::extract(&load_puzzle(opts, day)?);
+    if blocks.is_empty() {
+        println!("no examples found for day {}", day);
+        return Ok(());
+    }
+    std::fs::create_dir_all(out_dir)?;
+    for (i, block) in blocks.iter().enumerate() {
+        let path = out_dir.join(format!("example-{}-{}-{}.in", opts.year, day, i + 1));
+        if path.exists() && !force {
+            println!("skipped {} (already exists)", path.display());
+            continue;
+        }
+        std::fs::write(&path, block).with_context(|| format!("write {}", path.display()))?;
+        println!("wrote {} ({} lines)", path.display(), block.lines().count());
+    }
+    Ok(())
+}
+
+fn run_crosscheck(
+    opts: &Options,
+    day: Option<usize>,
+    python: &str,
+    scripts_dir: &Path,
+    input: Option<String>,
+) -> Result<()> {
+    let days = match day {
+        Some(d) if crosscheck::scripts(opts.year, d).is_empty() => {
+            return Err(anyhow!("no reference script for day {}", d))
+        }
+        Some(d) => vec![d],
+        None if input.is_some() => return Err(anyhow!("--input requires a day")),
+        None => crosscheck::days(opts.year),
+    };
+    let mut mismatches = 0;
+    for day in days {
+        let input = match input {
+            Some(ref i) => i.clone(),
+            None => day_input(opts, day)?,
+        };
+        let results = dispatch(day, opts, Some(input.clone()))?;
+        for script in crosscheck::scripts(opts.year, day) {
+            let answers = crosscheck::run_script(python, &scripts_dir.join(script), &input)?;
+            for r in &results {
+                let answer = match r.answer {
+                    Ok(a) => a,
+                    Err(ref e) => {
+                        mismatches += 1;
+                        println!("{}: failed: {}", r.label(), e);
+                        continue;
+                    }
+                };
+                match answers.get(r.part - 1) {
+                    Some(&a) if a == answer => {
+                        println!("{}: {} (agrees with {})", r.label(), answer, script)
+                    }
+                    Some(&a) => {
+                        mismatches += 1;
+                        println!("{}: {} but {} gives {}", r.label(), answer, script, a);
+                    }
+                    None => {
+                        mismatches += 1;
+                        println!("{}: {} but {} gives no answer", r.label(), answer, script);
+                    }
+                }
+            }
+        }
+    }
+    if mismatches > 0 {
+        return Err(anyhow!(
+            "{} answer(s) differ from the reference scripts",
+            mismatches
+        ));
+    }
+    Ok(())
+}
+
+/// A list of what a command could have used instead, for error messages.
+fn available(names: &[String]) -> String {
+    match names {
+        [] => "none".to_owned(),
+        _ => names.join(", "),
+    }
+}
+
+fn find_generator(year: usize, day: usize) -> Result<&'static generate::Generator> {
+    let found = registry::find(year, day).and_then(|d| d.generator.as_ref());
+    found.ok_or_else(|| {
+        let days = registry::days(year)
+            .into_iter()
+            .filter_map(|d| Some(format!("{} ({})", d.day, d.generator.as_ref()?.size)))
+            .collect::<Vec<_>>();
+        anyhow!(
+            "no input generator for day {} of {}; available: {}",
+            day,
+            year,
+            available(&days)
+        )
+    })
+}
+
+fn generate_input(
+    opts: &Options,
+    day: usize,
+    size: Option<usize>,
+    seed: Option<u64>,
+) -> Result<()> {
+    let generator = find_generator(opts.year, day)?;
+    let seed = seed.unwrap_or_else(|| {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        let seed = now.as_nanos() as u64;
+        eprintln!("seed: {}", seed);
+        seed
+    });
+    let size = size.unwrap_or(generator.default_size);
+    print!("{}", generator.input(seed, size)?);
+    Ok(())
+}
+
+fn bench(opts: &Options, day: usize, size: Option<usize>, seed: u64, runs: usize) -> Result<()> {
+    if runs == 0 {
+        return Err(anyhow!("invalid value for runs: {}", runs));
+    }
+    let generator = find_generator(opts.year, day)?;
+    let size = size.unwrap_or(generator.default_size);
+    let input = generator.input(seed, size)?;
+    println!(
+        "Day {}, {} = {}, seed {}, best of {} runs",
+        day, generator.size, size, seed, runs
+    );
+    let mut best: Vec<PartResult> = Vec::new();
+    for _ in 0..runs {
+        for r in dispatch(day, opts, Some(input.clone()))? {
+            match best
+                .iter_mut()
+                .find(|b| b.part == r.part && b.solution == r.solution)
+            {
+                Some(b) => b.elapsed = b.elapsed.min(r.elapsed),
+                None => best.push(r),
+            }
+        }
+    }
+    best.sort_by_key(|r| r.part);
+    println!();
+    println!("Part  {:<10}  {:<20}  {:>8}", "Solution", "Answer", "Time");
+    for r in &best {
+        println!(
+            "{:>4}  {:<10}  {:<20}  {:>8}",
+            r.part,
+            r.solution,
+            r.answer_text(),
+            display_duration(r.elapsed)
+        );
+    }
+    check_failures(&best)
+}
+
+fn export_day(
+    opts: &Options,
+    day: usize,
+    format: export::Format,
+    out: Option<&Path>,
+    input: Option<String>,
+) -> Result<()> {
+    let found = registry::find(opts.year, day).and_then(|d| d.exporter.as_ref());
+    let exporter = found.ok_or_else(|| {
+        let days = registry::days(opts.year)
+            .into_iter()
+            .filter_map(|d| Some(format!("{} ({})", d.day, d.exporter.as_ref()?.what)))
+            .collect::<Vec<_>>();
+        anyhow!(
+            "no export for day {} of {}; available: {}",
+            day,
+            opts.year,
+            available(&days)
+        )
+    })?;
+    let input = match input {
+        Some(i) => i,
+        None => day_input(opts, day)?,
     };
+    let exported = (exporter.export)(&input, format, &opts.params)?;
+    match out {
+        Some(path) => {
+            std::fs::write(path, exported).with_context(|| format!("write {}", path.display()))
+        }
+        None => {
+            print!("{}", exported);
+            Ok(())
+        }
+    }
 }
 
-fn dispatch(day: usize, part: Option<usize>, input: Option<String>) -> Result<()> {
-    let entry_points = [
-        day1::run,
-        day2::run,
-        day3::run,
-        day4::run,
-        day5::run,
-        day6::run,
-        day7::run,
-        day8::run,
-        day9::run,
-        day10::run,
-        day11::run,
-        day12::run,
-        day13::run,
-        day14::run,
-        day15::run,
-        day16::run,
-        day17::run,
-        day18::run,
-        day19::run,
-        day20::run,
-        day21::run,
-        day22::run,
-        day23::run,
-        day24::run,
-        day25::run,
-    ];
-    let run = match entry_points.get(day - 1) {
-        Some(r) => r,
-        None => return Ok(()),
+fn answers_key(year: usize) -> String {
+    format!("answers/{}", year)
+}
+
+/// Run every day on `jobs` worker threads, printing the results in day order as they become
+/// available and a summary of the whole year at the end.
+fn run_all(opts: &Options, jobs: usize, slowest: usize) -> Result<()> {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::mpsc;
+    use std::time::Instant;
+
+    let days = registry::days(opts.year)
+        .into_iter()
+        .map(|d| d.day)
+        .collect::<Vec<_>>();
+    let days = match opts.inputs_dir {
+        Some(ref dir) => {
+            let days = days
+                .into_iter()
+                .filter(|&d| inputs_dir_path(dir, d).is_file())
+                .collect::<Vec<_>>();
+            if days.is_empty() {
+                return Err(anyhow!(
+                    "no inputs named 1.txt to 25.txt in {}",
+                    dir.display()
+                ));
+            }
+            days
+        }
+        None => days,
     };
-    let cache_key = format!("input/20{}/{}", YEAR, day);
+    let next = AtomicUsize::new(0);
+    let clock = Instant::now();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::scope(|s| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let (days, next) = (&days, &next);
+            s.spawn(move || {
+                while let Some(&day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let (result, log) = log::capture(|| dispatch(day, opts, None));
+                    if tx.send((day, log, result)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        // Buffer results that finish early until every day before them has been printed.
+        let mut pending = days.iter().map(|_| None).collect::<Vec<_>>();
+        let mut printed = 0;
+        let mut results = Vec::new();
+        for (day, log, result) in rx {
+            pending[days.iter().position(|d| *d == day).unwrap()] = Some((log, result));
+            while let Some((log, result)) = pending.get_mut(printed).and_then(Option::take) {
+                print!("{}", log);
+                for r in result? {
+                    print_result(&r);
+                    results.push(r);
+                }
+                printed += 1;
+            }
+        }
+        // The recorded answers are for our own inputs, so they can't verify anyone else's.
+        let answers = match opts.inputs_dir {
+            Some(_) => Answers::default(),
+            None => Answers::load(&opts.cache.path(&answers_key(opts.year)))?,
+        };
+        print_summary(&results, &answers, clock.elapsed(), slowest);
+        check_failures(&results)
+    })
+}
+
+/// Run one day against each of `inputs`, reporting the answers for each input side by side.
+fn run_inputs(day: usize, opts: &Options, names: &[String], inputs: Vec<String>) -> Result<()> {
+    let width = names.iter().map(|n| n.len()).max().unwrap_or(0).max(5);
+    println!("{:<width$}  Part  {:<20}  {:>8}", "Input", "Answer", "Time");
+    let mut all = Vec::new();
+    for (name, input) in names.iter().zip(inputs) {
+        let results = dispatch(day, opts, Some(input)).with_context(|| format!("run {}", name))?;
+        for r in results {
+            println!(
+                "{:<width$}  {:>4}  {:<20}  {:>8}",
+                name,
+                r.part,
+                r.answer_text(),
+                display_duration(r.elapsed),
+            );
+            if let Err(ref e) = r.answer {
+                println!("{:<width$}        {}", "", e);
+            }
+            all.push(r);
+        }
+    }
+    check_failures(&all)
+}
+
+fn print_summary(results: &[PartResult], answers: &Answers, wall: Duration, slowest: usize) {
+    println!("Day  Part  {:<20}  {:>8}  Status", "Answer", "Time");
+    for r in results {
+        println!(
+            "{:>3}  {:>4}  {:<20}  {:>8}  {}{}",
+            r.day,
+            r.part,
+            r.answer_text(),
+            display_duration(r.elapsed),
+            match r.answer {
+                Ok(a) => answers.verify(r.day, r.part, a).to_string(),
+                Err(ref e) => e.clone(),
+            },
+            r.suffix()
+        );
+    }
+    println!();
+
+    // Parts run concurrently with `-j`, so their times add up to more than the wall clock.
+    let summed = results.iter().map(|r| r.elapsed).sum::<Duration>();
+    println!(
+        "Total: {} wall, {} summed over parts",
+        display_duration(wall),
+        display_duration(summed)
+    );
+
+    let mut by_time = results.iter().collect::<Vec<_>>();
+    by_time.sort_by_key(|r| std::cmp::Reverse(r.elapsed));
+    if slowest > 0 && !by_time.is_empty() {
+        println!();
+        println!("Slowest:");
+        for r in by_time.iter().take(slowest) {
+            let share = r.elapsed.as_secs_f64() / summed.as_secs_f64() * 100.0;
+            println!(
+                "  Day {:>2}, Part {}  {:>8}  {:>5.1}%{}",
+                r.day,
+                r.part,
+                display_duration(r.elapsed),
+                share,
+                r.suffix()
+            );
+        }
+    }
+}
+
+fn dispatch(day: usize, opts: &Options, input: Option<String>) -> Result<Vec<PartResult>> {
+    let run = registry::find(opts.year, day)
+        .ok_or_else(|| anyhow!("no solution for day {} of {}", day, opts.year))?
+        .run;
     let input = match input {
         Some(i) => i,
-        None => cached(&cache_key, || fetch_input(day))?,
+        None => day_input(opts, day)?,
     };
     let runner = Runner {
         input: input.trim(),
         day,
-        part,
+        opts,
+        results: RefCell::new(Vec::new()),
+        error: RefCell::new(None),
+        params_read: RefCell::new(Vec::new()),
     };
     run(&runner);
+    if let Some(e) = runner.error.into_inner() {
+        return Err(e);
+    }
+    let read = runner.params_read.into_inner();
+    if let Some(name) = opts.params.names().find(|n| !read.contains(n)) {
+        return Err(anyhow!("day {} has no parameter {:?}", day, name));
+    }
+    let results = runner.results.into_inner();
+    check_agreement(&results)?;
+    Ok(results)
+}
+
+/// The answer to a single part of a day, along with how long it took to compute.
+#[derive(Debug, Clone)]
+struct PartResult {
+    day: usize,
+    part: usize,
+    /// The name of the solution that produced the answer, for days with several.
+    solution: &'static str,
+    /// The answer, or why the part failed.
+    answer: Result<usize, String>,
+    elapsed: Duration,
+}
+
+impl PartResult {
+    fn label(&self) -> String {
+        format!("Day {}, Part {}{}", self.day, self.part, self.suffix())
+    }
+
+    /// The answer as shown in tables.
+    fn answer_text(&self) -> String {
+        match self.answer {
+            Ok(a) => a.to_string(),
+            Err(_) => "failed".to_owned(),
+        }
+    }
+
+    /// The name of a non-default solution, for appending to a label.
+    fn suffix(&self) -> String {
+        match self.solution {
+            "default" => String::new(),
+            name => format!(" ({})", name),
+        }
+    }
+}
+
+/// Check that every solution of a day that produced an answer gave the same answers.
+fn check_agreement(results: &[PartResult]) -> Result<()> {
+    let answered = results
+        .iter()
+        .filter(|r| r.answer.is_ok())
+        .collect::<Vec<_>>();
+    for r in &answered {
+        let first = answered
+            .iter()
+            .find(|f| f.part == r.part)
+            .expect("r itself");
+        if r.answer != first.answer {
+            return Err(anyhow!(
+                "solutions disagree on day {}, part {}: {} gives {} but {} gives {}",
+                r.day,
+                r.part,
+                first.solution,
+                first.answer_text(),
+                r.solution,
+                r.answer_text()
+            ));
+        }
+    }
     Ok(())
 }
 
+/// Fail if any part failed, once its results have been reported.
+fn check_failures(results: &[PartResult]) -> Result<()> {
+    match results.iter().filter(|r| r.answer.is_err()).count() {
+        0 => Ok(()),
+        1 => Err(anyhow!("1 part failed")),
+        n => Err(anyhow!("{} parts failed", n)),
+    }
+}
+
+fn print_result(result: &PartResult) {
+    if result.day < 10 {
+        print!(" ");
+    }
+    println!("{}: {}", result.label(), result.answer_text());
+    match result.answer {
+        Ok(_) => println!("                {}", display_duration(result.elapsed)),
+        Err(ref e) => println!("                {}", e),
+    }
+    println!();
+}
+
 pub struct Runner<'a> {
     input: &'a str,
     day: usize,
-    part: Option<usize>,
+    opts: &'a Options,
+    results: RefCell<Vec<PartResult>>,
+    /// Why the day could not be run at all, as opposed to a part failing.
+    error: RefCell<Option<anyhow::Error>>,
+    /// The parameters the day looked for, so that any others can be rejected.
+    params_read: RefCell<Vec<&'static str>>,
 }
 
 impl<'a> Runner<'a> {
-    pub fn run<I, P, F1, F2>(&self, parse: P, part_one: F1, part_two: F2)
+    pub fn input(&self) -> &'a str {
+        self.input
+    }
+
+    pub fn explain(&self) -> bool {
+        self.opts.explain
+    }
+
+    pub fn log(&self) -> Log {
+        self.opts.log
+    }
+
+    /// The value given for one of the day's parameters with `--param NAME=VALUE`. A value that
+    /// doesn't parse fails the day.
+    pub fn param<T>(&self, name: &'static str) -> Option<T>
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.params_read.borrow_mut().push(name);
+        self.opts.params.get(name).unwrap_or_else(|e| {
+            self.fail(e);
+            None
+        })
+    }
+
+    /// Stop the day from reporting any answers, keeping the first reason given.
+    fn fail(&self, e: anyhow::Error) {
+        self.error.borrow_mut().get_or_insert(e);
+    }
+
+    pub fn run<I, P, F1, F2, A1, A2>(&self, parse: P, part_one: F1, part_two: F2)
     where
         I: Clone,
         P: Fn(&'a str) -> I,
-        F1: Fn(I) -> usize,
-        F2: Fn(I) -> usize,
+        F1: Fn(I) -> A1,
+        F2: Fn(I) -> A2,
+        A1: Answer,
+        A2: Answer,
     {
-        let i = parse(self.input);
-        self.run_part(1, || part_one(i.clone()));
-        self.run_part(2, || part_two(i));
+        if let Some(ref name) = self.opts.solution {
+            return self.fail(anyhow!(
+                "day {} has only one solution, so there is no {:?} to choose",
+                self.day,
+                name
+            ));
+        }
+        self.run_parts(
+            "default",
+            |input| Ok(parse(input)),
+            |i| part_one(i).into_result(),
+            |i| part_two(i).into_result(),
+        );
+    }
+
+    /// Run a day with several solutions. Only the first, or the one chosen with `--solution`,
+    /// is run unless `--variants` was given, in which case they all are and their answers are
+    /// checked against each other.
+    pub fn solve(&self, solutions: &[&dyn AnySolution<'a>]) {
+        if self.opts.variants {
+            solutions.iter().for_each(|s| s.run(self));
+            return;
+        }
+        let name = match self.opts.solution {
+            Some(ref name) => name,
+            None => return solutions[0].run(self),
+        };
+        match solutions.iter().find(|s| s.name() == name) {
+            Some(s) => s.run(self),
+            None => {
+                let names = solutions.iter().map(|s| s.name()).collect::<Vec<_>>();
+                self.fail(anyhow!(
+                    "day {} has no solution named {:?}; available: {}",
+                    self.day,
+                    name,
+                    names.join(", ")
+                ));
+            }
+        }
+    }
+
+    fn run_parts<I, P, F1, F2>(&self, name: &'static str, parse: P, part_one: F1, part_two: F2)
+    where
+        I: Clone,
+        P: Fn(&'a str) -> Result<I>,
+        F1: Fn(I) -> Result<usize>,
+        F2: Fn(I) -> Result<usize>,
+    {
+        let i = match parse(self.input) {
+            Ok(i) => i,
+            Err(e) => {
+                // Neither part can run, so both report why.
+                let e = format!("{:#}", e.context("parse input"));
+                self.run_part(name, 1, || Err(anyhow!("{}", e)));
+                self.run_part(name, 2, || Err(anyhow!("{}", e)));
+                return;
+            }
+        };
+        self.run_part(name, 1, || part_one(i.clone()));
+        self.run_part(name, 2, || part_two(i));
     }
 
-    fn run_part<F>(&self, part: usize, f: F)
+    fn run_part<F>(&self, solution: &'static str, part: usize, f: F)
     where
-        F: FnOnce() -> usize,
+        F: FnOnce() -> Result<usize>,
     {
-        match self.part {
+        match self.opts.part {
             Some(p) if p != part => return,
             _ => {}
         }
         use std::time::Instant;
 
         let clock = Instant::now();
-        let output = f();
+        let answer = f().map_err(|e| format!("{:#}", e));
         let elapsed = clock.elapsed();
-        if self.day < 10 {
-            print!(" ");
-        }
-        println!("Day {}, Part {}: {}", self.day, part, output);
-        println!("                {}", display_duration(elapsed));
-        println!();
+        self.results.borrow_mut().push(PartResult {
+            day: self.day,
+            part,
+            solution,
+            answer,
+            elapsed,
+        });
     }
 }
 
-fn display_duration(duration: std::time::Duration) -> String {
+fn display_duration(duration: Duration) -> String {
     let val = duration.as_nanos();
     let mut divisor = 1;
 
@@ -256,5 +1188,5 @@ fn display_duration(duration: std::time::Duration) -> String {
         divisor *= 1000;
         unit = u;
     }
-    return format!("{}{}", val / divisor, unit);
+    format!("{}{}", val / divisor, unit)
 }
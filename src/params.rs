@@ -0,0 +1,80 @@
+//! Settings for a single day given on the command line with `--param NAME=VALUE`, such as the
+//! number of beacons two day 19 scanners must share.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use anyhow::Result;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    pub name: String,
+    pub value: String,
+}
+
+impl FromStr for Param {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_once('=') {
+            Some((name, value)) if !name.is_empty() => Ok(Param {
+                name: name.to_owned(),
+                value: value.to_owned(),
+            }),
+            _ => Err(anyhow!("expected NAME=VALUE, found {:?}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Params(Vec<Param>);
+
+impl Params {
+    pub fn new(params: Vec<Param>) -> Self {
+        Params(params)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|p| p.name.as_str())
+    }
+
+    /// The value of `name`, if it was given. The last value wins if it was given twice.
+    pub fn get<T>(&self, name: &str) -> Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match self.0.iter().rev().find(|p| p.name == name) {
+            Some(p) => p
+                .value
+                .parse()
+                .map(Some)
+                .map_err(|e| anyhow!("invalid value for {}: {:?}: {}", name, p.value, e)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn get() {
+        let params = Params::new(vec![
+            "min-overlap=3".parse().unwrap(),
+            "point=1,2,3".parse().unwrap(),
+            "min-overlap=4".parse().unwrap(),
+        ]);
+        assert_eq!(params.get::<usize>("min-overlap").unwrap(), Some(4));
+        assert_eq!(params.get::<usize>("missing").unwrap(), None);
+        assert!(params.get::<usize>("point").is_err());
+        assert!("no-value".parse::<Param>().is_err());
+        assert!("=1".parse::<Param>().is_err());
+    }
+}
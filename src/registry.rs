@@ -0,0 +1,60 @@
+//! The solved days, by year. Each year's module declares its day modules and lists their
+//! `DAY`s; adding a year means adding its module and listing it in `YEARS`.
+
+use crate::export::Exporter;
+use crate::generate::Generator;
+use crate::Runner;
+
+const YEARS: &[&[&Day]] = &[crate::y2021::DAYS];
+
+/// A solution to one day's puzzle.
+pub struct Day {
+    pub year: usize,
+    pub day: usize,
+    pub title: &'static str,
+    pub run: fn(&Runner),
+    /// Produces random inputs for stress and scaling tests.
+    pub generator: Option<Generator>,
+    /// Writes out what the solution reconstructs from its input.
+    pub exporter: Option<Exporter>,
+}
+
+fn all() -> impl Iterator<Item = &'static Day> {
+    YEARS.iter().flat_map(|days| days.iter().copied())
+}
+
+/// The years with registered days, in order.
+pub fn years() -> Vec<usize> {
+    let mut years = all().map(|d| d.year).collect::<Vec<_>>();
+    years.sort_unstable();
+    years.dedup();
+    years
+}
+
+/// The registered days of `year`, in order.
+pub fn days(year: usize) -> Vec<&'static Day> {
+    let mut days = all().filter(|d| d.year == year).collect::<Vec<_>>();
+    days.sort_by_key(|d| d.day);
+    days
+}
+
+pub fn find(year: usize, day: usize) -> Option<&'static Day> {
+    all().find(|d| d.year == year && d.day == day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn registered_once() {
+        let days = days(2021);
+        assert_eq!(
+            days.iter().map(|d| d.day).collect::<Vec<_>>(),
+            (1..=25).collect::<Vec<_>>()
+        );
+        assert!(days.iter().all(|d| !d.title.is_empty()));
+        assert!(find(2021, 26).is_none());
+        assert_eq!(years(), vec![2021]);
+    }
+}
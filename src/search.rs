@@ -39,7 +39,7 @@ impl<T> Eq for WithCost<T> {}
 
 impl<T> PartialOrd for WithCost<T> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.0.partial_cmp(&other.0)
+        Some(self.cmp(other))
     }
 }
 
@@ -0,0 +1,135 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use crate::cache::Cache;
+use crate::client::Client;
+use crate::client::FetchError;
+use crate::prelude::*;
+
+const SESSION_KEY: &str = "session";
+
+/// Read the session token from `AOC_TOKEN`, falling back to the `session` file in the cache.
+pub fn load(cache: &Cache) -> Result<String> {
+    if let Ok(val) = std::env::var("AOC_TOKEN") {
+        return Ok(val.trim().to_owned());
+    }
+    match cache.get(SESSION_KEY)? {
+        Some(token) => Ok(token.trim().to_owned()),
+        None => Err(anyhow!(
+            "session token not found at '{}'",
+            cache.path(SESSION_KEY).display()
+        )),
+    }
+}
+
+/// Store the session token in the cache, readable and writable only by the current user.
+pub fn store(cache: &Cache, token: &str) -> Result<()> {
+    let token = token.trim();
+    validate(token)?;
+    let path = cache.path(SESSION_KEY);
+    std::fs::create_dir_all(path.parent().expect("non-empty path"))?;
+    open_private(&path)
+        .and_then(|mut f| f.write_all(token.as_bytes()))
+        .with_context(|| format!("write session token to {}", path.display()))
+}
+
+/// Open `path` for writing such that only the current user can read it, tightening the
+/// permissions of an existing file as well.
+#[cfg(unix)]
+fn open_private(path: &Path) -> std::io::Result<File> {
+    use std::fs::OpenOptions;
+    use std::os::unix::fs::OpenOptionsExt;
+    use std::os::unix::fs::PermissionsExt;
+
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    Ok(file)
+}
+
+#[cfg(not(unix))]
+fn open_private(path: &Path) -> std::io::Result<File> {
+    File::create(path)
+}
+
+/// Check that the token at least looks like a session cookie, so an obviously wrong value is
+/// caught without a round trip to the server.
+pub fn validate(token: &str) -> Result<()> {
+    if token.is_empty() {
+        return Err(anyhow!("session token is empty"));
+    }
+    if let Some(c) = token.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(anyhow!(
+            "session token contains '{}'; expected the hex value of the 'session' cookie",
+            c
+        ));
+    }
+    Ok(())
+}
+
+/// Ask the server whether it accepts the token.
+pub fn check(client: &Client, token: &str) -> Result<()> {
+    validate(token)?;
+    match client.check_session(token) {
+        Ok(()) => Ok(()),
+        Err(FetchError::SessionExpired) => Err(anyhow!(
+            "session token was rejected by the server; log in again and update it"
+        )),
+        Err(e) => Err(e).context("check session token"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::client::mock::Server;
+
+    const TOKEN: &str = "53616c7465645f5f0123456789abcdef";
+
+    #[test]
+    fn validate_format() {
+        assert!(validate(TOKEN).is_ok());
+        assert!(validate("").is_err());
+        assert!(validate("session=abc").is_err());
+    }
+
+    #[test]
+    fn check_against_server() {
+        let server = Server::start(|req| match req.header("cookie") {
+            Some(c) if c == format!("session={}", TOKEN) => (200, vec![], "settings".to_owned()),
+            _ => (
+                302,
+                vec![("Location", "/auth/login".to_owned())],
+                String::new(),
+            ),
+        });
+        let client = Client::new(&server.url).with_min_interval(Duration::ZERO);
+        assert!(check(&client, TOKEN).is_ok());
+        let err = check(&client, "abcdef").unwrap_err();
+        assert!(err.to_string().contains("rejected"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn store_restricts_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("aoc-session-{}", std::process::id()));
+        let cache = Cache::new(&dir);
+        store(&cache, &format!("{}\n", TOKEN)).unwrap();
+        assert_eq!(cache.get(SESSION_KEY).unwrap().as_deref(), Some(TOKEN));
+        let mode = std::fs::metadata(cache.path(SESSION_KEY))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,63 @@
+//! Solutions to a day's puzzle. Most days have one, given to `Runner::run` as separate
+//! functions; days with alternatives implement `Solution` for each and pass them all to
+//! `Runner::solve`.
+
+use anyhow::Result;
+
+use crate::Runner;
+
+/// One way of solving a day's puzzle.
+pub trait Solution<'a> {
+    type Input: Clone;
+
+    fn name(&self) -> &'static str {
+        "default"
+    }
+
+    fn parse(&self, input: &'a str) -> Result<Self::Input>;
+
+    fn part_one(&self, input: Self::Input) -> Result<usize>;
+
+    fn part_two(&self, input: Self::Input) -> Result<usize>;
+}
+
+/// A `Solution` with its input type erased, so that solutions with different inputs can be
+/// run side by side.
+pub trait AnySolution<'a> {
+    fn name(&self) -> &'static str;
+
+    fn run(&self, runner: &Runner<'a>);
+}
+
+impl<'a, S: Solution<'a>> AnySolution<'a> for S {
+    fn name(&self) -> &'static str {
+        Solution::name(self)
+    }
+
+    fn run(&self, runner: &Runner<'a>) {
+        runner.run_parts(
+            Solution::name(self),
+            |input| self.parse(input),
+            |input| self.part_one(input),
+            |input| self.part_two(input),
+        );
+    }
+}
+
+/// What a part given to `Runner::run` returns: the answer, or a `Result` for parts that can
+/// reject their input.
+pub trait Answer {
+    fn into_result(self) -> Result<usize>;
+}
+
+impl Answer for usize {
+    fn into_result(self) -> Result<usize> {
+        Ok(self)
+    }
+}
+
+impl Answer for Result<usize> {
+    fn into_result(self) -> Result<usize> {
+        self
+    }
+}
@@ -0,0 +1,57 @@
+//! Advent of Code 2021. Each day module describes itself with a `DAY` constant.
+
+use crate::registry::Day;
+
+mod day1;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+mod day2;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+mod day8;
+mod day9;
+
+pub const DAYS: &[&Day] = &[
+    &day1::DAY,
+    &day2::DAY,
+    &day3::DAY,
+    &day4::DAY,
+    &day5::DAY,
+    &day6::DAY,
+    &day7::DAY,
+    &day8::DAY,
+    &day9::DAY,
+    &day10::DAY,
+    &day11::DAY,
+    &day12::DAY,
+    &day13::DAY,
+    &day14::DAY,
+    &day15::DAY,
+    &day16::DAY,
+    &day17::DAY,
+    &day18::DAY,
+    &day19::DAY,
+    &day20::DAY,
+    &day21::DAY,
+    &day22::DAY,
+    &day23::DAY,
+    &day24::DAY,
+    &day25::DAY,
+];
@@ -21,6 +21,15 @@ fn part_two(nums: Vec<usize>) -> usize {
         .count()
 }
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 1,
+    title: "Sonar Sweep",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
     runner.run(parse, part_one, part_two);
 }
@@ -1,5 +1,14 @@
 use crate::prelude::*;
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 10,
+    title: "Syntax Scoring",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
     runner.run(parse, part_one, part_two);
 }
@@ -1,5 +1,14 @@
 use crate::prelude::*;
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 11,
+    title: "Dumbo Octopus",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
     runner.run(parse, part_one, part_two);
 }
@@ -0,0 +1,159 @@
+use crate::generate::Generator;
+use crate::generate::Rng;
+use crate::prelude::*;
+
+const MAX_NODES: usize = 32;
+
+#[derive(Debug, Clone)]
+struct Graph {
+    start: usize,
+    end: usize,
+    small: Vec<bool>,
+    edges: HashMap<usize, Vec<usize>>,
+}
+
+fn parse(input: &str) -> Graph {
+    let iter = input.lines().map(|line| {
+        let mut split = line.split('-');
+        let from = split.next().unwrap();
+        let to = split.next().unwrap();
+        (from, to)
+    });
+    let mut ids = HashMap::default();
+    let mut small = Vec::new();
+    let mut create_node = |k: &str| {
+        *ids.entry(k.to_owned()).or_insert_with(|| {
+            small.push(k.chars().all(|c| c.is_lowercase()));
+            small.len() - 1
+        })
+    };
+    let start = create_node("start");
+    let end = create_node("end");
+    let mut edges = HashMap::default();
+    for (k, v) in iter {
+        let kid = create_node(k);
+        let vid = create_node(v);
+        edges.entry(kid).or_insert_with(Vec::new).push(vid);
+        if kid != start && vid != end {
+            edges.entry(vid).or_insert_with(Vec::new).push(kid);
+        }
+    }
+    if small.len() > MAX_NODES {
+        panic!("too many nodes in input: {} > {}", small.len(), MAX_NODES);
+    }
+    Graph {
+        start,
+        end,
+        small,
+        edges,
+    }
+}
+
+fn part_one(graph: Graph) -> usize {
+    let visited = [false; MAX_NODES];
+    search(&graph, graph.start, visited, false)
+}
+
+fn part_two(graph: Graph) -> usize {
+    let visited = [false; MAX_NODES];
+    search(&graph, graph.start, visited, true)
+}
+
+fn search(
+    graph: &Graph,
+    node: usize,
+    mut visited: [bool; MAX_NODES],
+    can_visit_twice: bool,
+) -> usize {
+    if node == graph.end {
+        return 1;
+    }
+    visited[node] = graph.small[node];
+    let edges = graph.edges.get(&node);
+    edges
+        .iter()
+        .flat_map(|v| v.iter().cloned())
+        .filter(|&n| !visited[n] || can_visit_twice)
+        .map(|n| search(graph, n, visited, !visited[n] && can_visit_twice))
+        .sum()
+}
+
+/// A random cave system with `size` caves besides the start and end (at most 30), about a
+/// quarter of them big. Big caves are never joined to each other, which would allow infinitely
+/// many paths.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut names = vec!["start".to_owned()];
+    let mut big = vec![false];
+    for i in 0..size {
+        let is_big = rng.below(4) == 0;
+        let letter = |n: usize| (b'a' + n as u8) as char;
+        let name = format!("{}{}", letter(i / 26), letter(i % 26));
+        names.push(if is_big { name.to_uppercase() } else { name });
+        big.push(is_big);
+    }
+    let end = names.len();
+    names.push("end".to_owned());
+    big.push(false);
+
+    let mut edges = Vec::new();
+    let connect = |a: usize, b: usize, edges: &mut Vec<(usize, usize)>| {
+        let joined = edges.contains(&(a, b)) || edges.contains(&(b, a));
+        if a != b && !(big[a] && big[b]) && !joined {
+            edges.push((a, b));
+        }
+    };
+    // Join each cave to one before it, so that every cave is reachable from the start.
+    for i in 1..=end {
+        let candidates = (0..i).filter(|&j| !(big[i] && big[j])).collect::<Vec<_>>();
+        let j = candidates[rng.below(candidates.len())];
+        connect(j, i, &mut edges);
+    }
+    for _ in 0..size / 2 {
+        let (a, b) = (rng.below(end + 1), rng.below(end + 1));
+        connect(a, b, &mut edges);
+    }
+    rng.shuffle(&mut edges);
+    edges
+        .iter()
+        .map(|&(a, b)| {
+            // The start must come first and the end last on their lines.
+            let (a, b) = if b == 0 || a == end { (b, a) } else { (a, b) };
+            format!("{}-{}\n", names[a], names[b])
+        })
+        .collect()
+}
+
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 12,
+    title: "Passage Pathing",
+    run,
+    generator: Some(Generator {
+        size: "number of caves",
+        default_size: 12,
+        max_size: Some(MAX_NODES - 2),
+        generate,
+    }),
+    exporter: None,
+};
+
+pub fn run(runner: &Runner) {
+    runner.run(parse, part_one, part_two);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generated() {
+        for seed in 0..20 {
+            let input = generate(&mut Rng::new(seed), 10);
+            let graph = parse(&input);
+            assert_eq!(graph.small.len(), 12);
+            let (one, two) = (part_one(graph.clone()), part_two(graph));
+            assert!(one >= 1, "seed {}: no path to the end", seed);
+            assert!(two >= one);
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use std::fmt::Display;
+
+use crate::log::Log;
 use crate::prelude::*;
 
 #[derive(Debug, Clone)]
@@ -29,13 +32,18 @@ impl Input {
             }
         }
     }
+}
 
-    fn display_paper(&self) {
-        let (max_x, max_y) = self.paper.iter().fold((0, 0), |(max_x, max_y), (x, y)| {
+/// Renders the dots on the paper, which spell out the answer to part two.
+struct Paper<'a>(&'a [(usize, usize)]);
+
+impl Display for Paper<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let (max_x, max_y) = self.0.iter().fold((0, 0), |(max_x, max_y), (x, y)| {
             (max_x.max(*x), max_y.max(*y))
         });
         let mut grid = vec![vec![false; max_x + 1]; max_y + 1];
-        for (x, y) in &self.paper {
+        for (x, y) in self.0 {
             grid[*y][*x] = true;
         }
         for row in &grid {
@@ -43,9 +51,9 @@ impl Input {
                 .iter()
                 .map(|b| if *b { '#' } else { ' ' })
                 .collect::<String>();
-            println!("{}", rowstr);
+            writeln!(f, "{}", rowstr)?;
         }
-        println!();
+        Ok(())
     }
 }
 
@@ -87,12 +95,22 @@ fn part_one(mut input: Input) -> usize {
     input.paper.iter().collect::<HashSet<_>>().len()
 }
 
-fn part_two(mut input: Input) -> usize {
+fn part_two(mut input: Input, log: Log) -> usize {
     input.fold();
-    input.display_paper();
+    log.debug(Paper(&input.paper));
     1234
 }
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 13,
+    title: "Transparent Origami",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
-    runner.run(parse, part_one, part_two);
+    let log = runner.log();
+    runner.run(parse, part_one, |input| part_two(input, log));
 }
@@ -6,7 +6,7 @@ struct Input<'a> {
     rules: HashMap<&'a str, [String; 2]>,
 }
 
-fn parse(input: &str) -> Input {
+fn parse(input: &str) -> Input<'_> {
     let mut lines = input.lines();
     let start = lines.next().unwrap().trim();
 
@@ -93,6 +93,15 @@ fn windows(s: &str) -> impl Iterator<Item = &str> {
         .map(|b| std::str::from_utf8(b).unwrap())
 }
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 14,
+    title: "Extended Polymerization",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
     runner.run(parse, part_one, part_two);
 }
@@ -58,7 +58,7 @@ fn cost_to(nums: &[Vec<usize>], y: isize, x: isize) -> usize {
     let yy = (y % tile_height) as usize;
     let xx = (x % tile_width) as usize;
 
-    let cost = nums[yy as usize][xx as usize] as isize + tile_x + tile_y;
+    let cost = nums[yy][xx] as isize + tile_x + tile_y;
     if (tile_y > 0 || tile_x > 0) && cost > 9 {
         (cost % 9) as usize
     } else {
@@ -66,6 +66,15 @@ fn cost_to(nums: &[Vec<usize>], y: isize, x: isize) -> usize {
     }
 }
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 15,
+    title: "Chiton",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
     runner.run(parse, part_one, part_two);
 }
@@ -16,6 +16,15 @@ fn part_two(message: &str) -> usize {
     parser.packet().unwrap().eval()
 }
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 16,
+    title: "Packet Decoder",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
     runner.run(parse, part_one, part_two);
 }
@@ -57,9 +66,9 @@ impl PacketKind {
             (Opcode::Product, _) => args.iter().map(|p| p.kind.eval()).product::<usize>(),
             (Opcode::Min, _) => args.iter().map(|p| p.kind.eval()).min().unwrap(),
             (Opcode::Max, _) => args.iter().map(|p| p.kind.eval()).max().unwrap(),
-            (Opcode::GreaterThan, &[ref a, ref b]) => (a.eval() > b.eval()) as usize,
-            (Opcode::LessThan, &[ref a, ref b]) => (a.eval() < b.eval()) as usize,
-            (Opcode::Equal, &[ref a, ref b]) => (a.eval() == b.eval()) as usize,
+            (Opcode::GreaterThan, [a, b]) => (a.eval() > b.eval()) as usize,
+            (Opcode::LessThan, [a, b]) => (a.eval() < b.eval()) as usize,
+            (Opcode::Equal, [a, b]) => (a.eval() == b.eval()) as usize,
             _ => panic!("invalid operation"),
         }
     }
@@ -183,6 +192,12 @@ impl<'a> Parser<'a> {
 mod test {
     use super::*;
 
+    fn packet(msg: &str) -> Result<(Packet, &str)> {
+        let mut parser = Parser { msg };
+        let packet = parser.packet()?;
+        Ok((packet, parser.msg))
+    }
+
     #[test]
     fn test_literal() {
         let (packet, s) = packet("110100101111111000101000").unwrap();
@@ -102,6 +102,15 @@ fn part_two(area: TargetArea) -> usize {
         .count()
 }
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 17,
+    title: "Trick Shot",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
     runner.run(parse, part_one, part_two);
 }
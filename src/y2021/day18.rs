@@ -0,0 +1,784 @@
+use std::fmt::Display;
+
+use crate::generate::Generator;
+use crate::generate::Rng;
+use crate::log::Log;
+use crate::prelude::*;
+
+#[derive(Debug, Clone)]
+struct Number(Inner, Inner);
+
+impl Number {
+    fn mag(&self) -> usize {
+        3 * self.0.mag() + 2 * self.1.mag()
+    }
+
+    fn reduce(&mut self) {
+        self.reduce_with(|_, _| {});
+    }
+
+    /// Reduce the number, calling `step` with each action taken and the number it leaves.
+    fn reduce_with<F>(&mut self, mut step: F)
+    where
+        F: FnMut(Action, &Number),
+    {
+        loop {
+            let action = if self.explode(1).is_some() {
+                Action::Explode
+            } else if self.split().is_some() {
+                Action::Split
+            } else {
+                return;
+            };
+            step(action, self);
+        }
+    }
+
+    fn explode(&mut self, depth: usize) -> Option<(usize, usize)> {
+        let exp = self.0.explode(depth);
+        if let (Some((a, b)), Number(_, right)) = (exp, &mut *self) {
+            // left sibling exploded
+            right.add_left(b);
+            return Some((a, 0));
+        }
+        if exp.is_some() {
+            return exp;
+        }
+        let exp = self.1.explode(depth);
+        if let (Some((a, b)), Number(left, _)) = (exp, &mut *self) {
+            // right sibling exploded
+            left.add_right(a);
+            return Some((0, b));
+        }
+        exp
+    }
+
+    fn split(&mut self) -> Option<()> {
+        if let left @ Some(_) = self.0.split() {
+            return left;
+        }
+        self.1.split()
+    }
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[{},{}]", self.0, self.1)
+    }
+}
+
+fn add(a: Number, b: Number) -> Number {
+    let mut sum = Number(Inner::Number(Box::new(a)), Inner::Number(Box::new(b)));
+    sum.reduce();
+    sum
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Explode,
+    Split,
+}
+
+/// The steps of adding two numbers, laid out like the puzzle's worked example: the sum, and
+/// then the number after each explode or split.
+fn trace_add(a: Number, b: Number) -> (Number, Vec<String>) {
+    let mut sum = Number(Inner::Number(Box::new(a)), Inner::Number(Box::new(b)));
+    let mut steps = vec![format!("after addition: {}", sum)];
+    sum.reduce_with(|action, n| {
+        let label = match action {
+            Action::Explode => "after explode:",
+            Action::Split => "after split:",
+        };
+        steps.push(format!("{:<16}{}", label, n));
+    });
+    (sum, steps)
+}
+
+#[derive(Debug, Clone)]
+enum Inner {
+    Val(usize),
+    Number(Box<Number>),
+}
+
+impl Display for Inner {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Inner::Val(n) => write!(f, "{}", n),
+            Inner::Number(n) => write!(f, "[{},{}]", n.0, n.1),
+        }
+    }
+}
+
+impl Inner {
+    fn explode(&mut self, depth: usize) -> Option<(usize, usize)> {
+        match self {
+            Inner::Number(n) => match (&n.0, &n.1) {
+                (&Inner::Val(left), &Inner::Val(right)) if depth >= 4 => {
+                    let mut cleared = Inner::Val(0);
+                    let retval = (left, right);
+                    std::mem::swap(&mut cleared, self);
+                    Some(retval)
+                }
+                _ => n.explode(depth + 1),
+            },
+            Inner::Val(_) => None,
+        }
+    }
+
+    fn add_right(&mut self, v: usize) {
+        match self {
+            Inner::Number(n) => n.1.add_right(v),
+            Inner::Val(ref mut n) => *n += v,
+        }
+    }
+
+    fn add_left(&mut self, v: usize) {
+        match self {
+            Inner::Number(n) => n.0.add_left(v),
+            Inner::Val(ref mut n) => *n += v,
+        }
+    }
+
+    fn mag(&self) -> usize {
+        match self {
+            Inner::Number(n) => n.mag(),
+            Inner::Val(n) => *n,
+        }
+    }
+
+    fn split(&mut self) -> Option<()> {
+        match self {
+            Inner::Number(n) => n.split(),
+            Inner::Val(n) if *n >= 10 => {
+                let left = *n / 2;
+                let right = left + (*n % 2);
+                *self = Inner::Number(Box::new(Number(Inner::Val(left), Inner::Val(right))));
+                Some(())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A regular number in a flattened snailfish number, with how many pairs enclose it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Regular {
+    value: usize,
+    depth: usize,
+}
+
+/// A snailfish number as its regular numbers from left to right. The depths are enough to
+/// recover the pairs, and adding or reducing numbers never has to walk a tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FlatNumber(Vec<Regular>);
+
+impl FlatNumber {
+    fn add(&self, other: &FlatNumber) -> FlatNumber {
+        let mut sum = FlatNumber(
+            self.0
+                .iter()
+                .chain(&other.0)
+                .map(|r| Regular {
+                    value: r.value,
+                    depth: r.depth + 1,
+                })
+                .collect(),
+        );
+        sum.reduce();
+        sum
+    }
+
+    /// Reduce the number. Until something splits only pairs nested inside four others can
+    /// explode, so they all go in one sweep. After that the splits go from left to right, with
+    /// any pair a split makes exploding straight away.
+    fn reduce(&mut self) {
+        if self.0.iter().all(|r| r.depth <= 5) {
+            self.explode_shallow();
+        } else {
+            self.explode_deep();
+        }
+
+        let regs = &mut self.0;
+        let mut i = 0;
+        while i < regs.len() {
+            let r = regs[i];
+            if r.value < 10 {
+                i += 1;
+                continue;
+            }
+            let (left, right) = (r.value / 2, r.value - r.value / 2);
+            if r.depth < 4 {
+                let depth = r.depth + 1;
+                regs[i] = Regular { value: left, depth };
+                regs.insert(
+                    i + 1,
+                    Regular {
+                        value: right,
+                        depth,
+                    },
+                );
+                // The left half may need splitting again.
+                continue;
+            }
+            // The new pair would be nested inside four others, so it explodes at once. The
+            // number to its left may now need splitting, and it comes first.
+            regs[i].value = 0;
+            if let Some(next) = regs.get_mut(i + 1) {
+                next.value += right;
+            }
+            if i > 0 {
+                regs[i - 1].value += left;
+                if regs[i - 1].value >= 10 {
+                    i -= 1;
+                }
+            }
+        }
+    }
+
+    /// Explode every pair of a number nested at most five deep, as a sum of reduced numbers
+    /// is. Then each regular number at depth five has the other half of its pair next to it.
+    fn explode_shallow(&mut self) {
+        let regs = &mut self.0;
+        let mut exploded = Vec::<Regular>::with_capacity(regs.len());
+        let mut carry = 0;
+        let mut i = 0;
+        while i < regs.len() {
+            let r = regs[i];
+            if r.depth > 4 {
+                if let Some(prev) = exploded.last_mut() {
+                    prev.value += r.value + carry;
+                }
+                carry = regs[i + 1].value;
+                exploded.push(Regular {
+                    value: 0,
+                    depth: r.depth - 1,
+                });
+                i += 2;
+            } else {
+                exploded.push(Regular {
+                    value: r.value + carry,
+                    depth: r.depth,
+                });
+                carry = 0;
+                i += 1;
+            }
+        }
+        *regs = exploded;
+    }
+
+    /// Explode every pair nested inside four others in a number nested any deeper, such as an
+    /// unreduced input. The leftmost pair of regular numbers goes first, which may leave its
+    /// parent a pair of regular numbers to explode next.
+    fn explode_deep(&mut self) {
+        let regs = &mut self.0;
+        let mut exploded = Vec::<Regular>::with_capacity(regs.len());
+        // The depth of each finished element swept so far, and whether it's a regular number
+        // rather than a pair. Two at the same depth on top are a pair of their own.
+        let mut stack = Vec::<(usize, bool)>::new();
+        let mut carry = 0;
+        for r in regs.iter() {
+            exploded.push(Regular {
+                value: r.value + carry,
+                depth: r.depth,
+            });
+            carry = 0;
+            stack.push((r.depth, true));
+            while let [.., (depth, left_regular), (right_depth, right_regular)] = stack[..] {
+                if depth != right_depth {
+                    break;
+                }
+                stack.truncate(stack.len() - 2);
+                if !(left_regular && right_regular && depth > 4) {
+                    stack.push((depth - 1, false));
+                    continue;
+                }
+                let right = exploded.pop().expect("pair on the stack");
+                let left = exploded.pop().expect("pair on the stack");
+                if let Some(prev) = exploded.last_mut() {
+                    prev.value += left.value;
+                }
+                carry += right.value;
+                exploded.push(Regular {
+                    value: 0,
+                    depth: depth - 1,
+                });
+                stack.push((depth - 1, true));
+            }
+        }
+        *regs = exploded;
+    }
+
+    fn mag(&self) -> usize {
+        let mut stack = Vec::<Regular>::new();
+        for &r in &self.0 {
+            stack.push(r);
+            while let [.., a, b] = stack[..] {
+                if a.depth != b.depth {
+                    break;
+                }
+                stack.truncate(stack.len() - 2);
+                stack.push(Regular {
+                    value: 3 * a.value + 2 * b.value,
+                    depth: a.depth - 1,
+                });
+            }
+        }
+        stack[0].value
+    }
+}
+
+impl From<&FlatNumber> for Number {
+    fn from(n: &FlatNumber) -> Self {
+        fn build<I>(regs: &mut std::iter::Peekable<I>, depth: usize) -> Inner
+        where
+            I: Iterator<Item = Regular>,
+        {
+            match regs.next_if(|r| r.depth == depth) {
+                Some(r) => Inner::Val(r.value),
+                None => {
+                    let left = build(regs, depth + 1);
+                    let right = build(regs, depth + 1);
+                    Inner::Number(Box::new(Number(left, right)))
+                }
+            }
+        }
+        let mut regs = n.0.iter().cloned().peekable();
+        let left = build(&mut regs, 1);
+        Number(left, build(&mut regs, 1))
+    }
+}
+
+impl Display for FlatNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        // Whether each pair still open has had its left side written.
+        let mut open = Vec::<bool>::new();
+        for r in &self.0 {
+            while open.len() < r.depth {
+                write!(f, "[")?;
+                open.push(false);
+            }
+            write!(f, "{}", r.value)?;
+            while let Some(left_done) = open.last_mut() {
+                if !*left_done {
+                    *left_done = true;
+                    write!(f, ",")?;
+                    break;
+                }
+                open.pop();
+                write!(f, "]")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<FlatNumber>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_number(line.trim_end()).with_context(|| format!("line {}", i + 1)))
+        .collect()
+}
+
+/// Parse a number with regular numbers of any size, nested however deeply. Errors give the
+/// column, counting from 1, where the number stops making sense.
+fn parse_number(line: &str) -> Result<FlatNumber> {
+    let mut regs = Vec::new();
+    // Whether each pair still open has had its comma.
+    let mut open = Vec::<bool>::new();
+    let mut chars = line.chars().enumerate().peekable();
+    let end = line.chars().count() + 1;
+    loop {
+        // An element: a regular number, or a pair opening as many more as it starts with.
+        while chars.next_if(|&(_, c)| c == '[').is_some() {
+            open.push(false);
+        }
+        match chars.next() {
+            Some((col, c)) if c.is_ascii_digit() && !open.is_empty() => {
+                let mut value = c.to_digit(10).expect("guard") as usize;
+                while let Some((_, d)) = chars.next_if(|(_, d)| d.is_ascii_digit()) {
+                    value = value
+                        .checked_mul(10)
+                        .and_then(|v| v.checked_add(d.to_digit(10).expect("guard") as usize))
+                        .ok_or_else(|| anyhow!("column {}: number too large", col + 1))?;
+                }
+                regs.push(Regular {
+                    value,
+                    depth: open.len(),
+                });
+            }
+            Some((col, c)) if open.is_empty() => {
+                return Err(anyhow!("column {}: expected '[', found {:?}", col + 1, c))
+            }
+            Some((col, c)) => {
+                return Err(anyhow!(
+                    "column {}: expected '[' or 0-9, found {:?}",
+                    col + 1,
+                    c
+                ))
+            }
+            None => return Err(anyhow!("column {}: unexpected end of line", end)),
+        }
+        // Close the pairs the element finishes, until one is waiting for its right side.
+        loop {
+            let expected = match open.last() {
+                Some(false) => ',',
+                Some(true) => ']',
+                None => match chars.next() {
+                    Some((col, c)) => {
+                        let col = col + 1;
+                        return Err(anyhow!(
+                            "column {}: expected end of line, found {:?}",
+                            col,
+                            c
+                        ));
+                    }
+                    None => return Ok(FlatNumber(regs)),
+                },
+            };
+            match chars.next() {
+                Some((_, ',')) if expected == ',' => {
+                    *open.last_mut().expect("open pair") = true;
+                    break;
+                }
+                Some((_, ']')) if expected == ']' => {
+                    open.pop();
+                }
+                Some((col, c)) => {
+                    let col = col + 1;
+                    return Err(anyhow!(
+                        "column {}: expected {:?}, found {:?}",
+                        col,
+                        expected,
+                        c
+                    ));
+                }
+                None => {
+                    return Err(anyhow!(
+                        "column {}: expected {:?}, found end of line",
+                        end,
+                        expected
+                    ))
+                }
+            }
+        }
+    }
+}
+
+/// How deeply `Tree` lets a number nest. Its methods recurse once per level, so a much deeper
+/// number would overflow the stack; `Flat` takes any depth.
+const MAX_TREE_DEPTH: usize = 100;
+
+/// Adds numbers as binary trees, reducing from the root again after every action.
+struct Tree;
+
+impl<'a> Solution<'a> for Tree {
+    type Input = Vec<Number>;
+
+    fn name(&self) -> &'static str {
+        "tree"
+    }
+
+    fn parse(&self, input: &'a str) -> Result<Vec<Number>> {
+        let ns = parse(input)?;
+        for (i, n) in ns.iter().enumerate() {
+            let depth = n.0.iter().map(|r| r.depth).max().unwrap_or(0);
+            if depth > MAX_TREE_DEPTH {
+                return Err(anyhow!(
+                    "line {}: nested {} deep, but the tree solution takes at most {}",
+                    i + 1,
+                    depth,
+                    MAX_TREE_DEPTH
+                ));
+            }
+        }
+        Ok(ns.iter().map(Number::from).collect())
+    }
+
+    fn part_one(&self, ns: Vec<Number>) -> Result<usize> {
+        let sum = ns.into_iter().reduce(add).ok_or_else(no_numbers)?;
+        Ok(sum.mag())
+    }
+
+    fn part_two(&self, ns: Vec<Number>) -> Result<usize> {
+        let max = ns
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| ns[i + 1..].iter().map(move |b| (a, b)))
+            .map(|(a, b)| {
+                let suma = add(a.clone(), b.clone()).mag();
+                let sumb = add(b.clone(), a.clone()).mag();
+                suma.max(sumb)
+            })
+            .max()
+            .ok_or_else(too_few_numbers)?;
+        Ok(max)
+    }
+}
+
+/// Adds flattened numbers, reducing each sum in a single sweep.
+struct Flat;
+
+impl<'a> Solution<'a> for Flat {
+    type Input = Vec<FlatNumber>;
+
+    fn parse(&self, input: &'a str) -> Result<Vec<FlatNumber>> {
+        parse(input)
+    }
+
+    fn part_one(&self, ns: Vec<FlatNumber>) -> Result<usize> {
+        let sum = ns
+            .into_iter()
+            .reduce(|a, b| a.add(&b))
+            .ok_or_else(no_numbers)?;
+        Ok(sum.mag())
+    }
+
+    fn part_two(&self, ns: Vec<FlatNumber>) -> Result<usize> {
+        let max = ns
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| ns[i + 1..].iter().map(move |b| (a, b)))
+            .map(|(a, b)| a.add(b).mag().max(b.add(a).mag()))
+            .max()
+            .ok_or_else(too_few_numbers)?;
+        Ok(max)
+    }
+}
+
+fn no_numbers() -> anyhow::Error {
+    anyhow!("no numbers to add")
+}
+
+fn too_few_numbers() -> anyhow::Error {
+    anyhow!("part two needs at least two numbers")
+}
+
+/// `size` random reduced numbers, one per line.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    fn number(rng: &mut Rng, depth: usize, out: &mut String) {
+        if depth > 0 && (depth == 4 || rng.below(3) == 0) {
+            out.push_str(&rng.below(10).to_string());
+            return;
+        }
+        out.push('[');
+        number(rng, depth + 1, out);
+        out.push(',');
+        number(rng, depth + 1, out);
+        out.push(']');
+    }
+    let mut input = String::new();
+    for _ in 0..size {
+        number(rng, 0, &mut input);
+        input.push('\n');
+    }
+    input
+}
+
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 18,
+    title: "Snailfish",
+    run,
+    generator: Some(Generator {
+        size: "number of snailfish numbers",
+        default_size: 100,
+        max_size: None,
+        generate,
+    }),
+    exporter: None,
+};
+
+pub fn run(runner: &Runner) {
+    if runner.explain() {
+        // The trace is built from trees, which may refuse an input `Flat` can still add up.
+        match Tree.parse(runner.input()) {
+            Ok(ns) => explain(&ns, runner.log()),
+            Err(e) => runner.log().info(format_args!("cannot explain: {:#}", e)),
+        }
+    }
+    runner.solve(&[&Flat, &Tree]);
+}
+
+/// Log every step of adding up the numbers, one addition after another.
+fn explain(ns: &[Number], log: Log) {
+    let mut iter = ns.iter().cloned();
+    let mut sum = match iter.next() {
+        Some(n) => n,
+        None => return,
+    };
+    for (i, n) in iter.enumerate() {
+        log.info(format_args!("addition {}: {} + {}", i + 1, sum, n));
+        let (next, steps) = trace_add(sum, n);
+        steps.iter().for_each(|s| log.info(s));
+        log.info("");
+        sum = next;
+    }
+    log.info(format_args!("sum: {} (magnitude {})", sum, sum.mag()));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const HOMEWORK: &str = "\
+[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]
+";
+
+    #[test]
+    fn homework() {
+        assert_eq!(Tree.part_one(Tree.parse(HOMEWORK).unwrap()).unwrap(), 4140);
+        assert_eq!(Tree.part_two(Tree.parse(HOMEWORK).unwrap()).unwrap(), 3993);
+        assert_eq!(Flat.part_one(Flat.parse(HOMEWORK).unwrap()).unwrap(), 4140);
+        assert_eq!(Flat.part_two(Flat.parse(HOMEWORK).unwrap()).unwrap(), 3993);
+    }
+
+    #[test]
+    fn flat_matches_tree() {
+        let input = generate(&mut Rng::new(2), 30);
+        let (trees, flats) = (Tree.parse(&input).unwrap(), Flat.parse(&input).unwrap());
+        for (tree, flat) in trees.iter().zip(&flats) {
+            assert_eq!(flat.to_string(), tree.to_string());
+            assert_eq!(flat.mag(), tree.mag());
+        }
+        let mut tree_sum = trees[0].clone();
+        let mut flat_sum = flats[0].clone();
+        for (tree, flat) in trees.iter().zip(&flats).skip(1) {
+            tree_sum = add(tree_sum, tree.clone());
+            flat_sum = flat_sum.add(flat);
+            assert_eq!(flat_sum.to_string(), tree_sum.to_string());
+        }
+    }
+
+    #[test]
+    fn worked_example() {
+        let ns = Tree
+            .parse("[[[[4,3],4],4],[7,[[8,4],9]]]\n[1,1]\n")
+            .unwrap();
+        let (sum, steps) = trace_add(ns[0].clone(), ns[1].clone());
+        assert_eq!(
+            steps,
+            vec![
+                "after addition: [[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]",
+                "after explode:  [[[[0,7],4],[7,[[8,4],9]]],[1,1]]",
+                "after explode:  [[[[0,7],4],[15,[0,13]]],[1,1]]",
+                "after split:    [[[[0,7],4],[[7,8],[0,13]]],[1,1]]",
+                "after split:    [[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]",
+                "after explode:  [[[[0,7],4],[[7,8],[6,0]]],[8,1]]",
+            ]
+        );
+        assert_eq!(sum.to_string(), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+    }
+
+    #[test]
+    fn reduce_deep_numbers() {
+        let reduced = |input: &str| {
+            let mut n = parse_number(input).unwrap();
+            n.reduce();
+            n.to_string()
+        };
+        assert_eq!(reduced("[[[[[9,8],1],2],3],4]"), "[[[[0,9],2],3],4]");
+        assert_eq!(reduced("[[[[[[1,2],3],4],5],6],7]"), "[[[[0,9],5],6],7]");
+        assert_eq!(reduced("[23,[1,0]]"), "[[[5,6],[6,6]],[1,0]]");
+
+        // Unreduced numbers, deeper and bigger than the puzzle's, give the same sums either way.
+        let mut rng = Rng::new(4);
+        let input = (0..20)
+            .map(|_| unreduced(&mut rng, 0))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(
+            Flat.part_one(Flat.parse(&input).unwrap()).unwrap(),
+            Tree.part_one(Tree.parse(&input).unwrap()).unwrap()
+        );
+        assert_eq!(
+            Flat.part_two(Flat.parse(&input).unwrap()).unwrap(),
+            Tree.part_two(Tree.parse(&input).unwrap()).unwrap()
+        );
+
+        let depth = 100_000;
+        let deep = format!("{}1{}", "[".repeat(depth), ",1]".repeat(depth));
+        let sum = parse_number(&deep)
+            .unwrap()
+            .add(&parse_number("[1,1]").unwrap());
+        assert!(sum.0.iter().all(|r| r.depth <= 4 && r.value < 10));
+    }
+
+    /// Every solution `run` registers either adds up a number nested far too deeply for a
+    /// recursive walk, or rejects it, rather than overflowing the stack.
+    #[test]
+    fn deep_numbers_in_every_solution() {
+        let depth = 100_000;
+        let input = format!("{}1{}\n[1,1]", "[".repeat(depth), ",1]".repeat(depth));
+        let ns = Flat.parse(&input).unwrap();
+        // Part two tries both orders of the only pair, including part one's.
+        assert!(Flat.part_one(ns.clone()).unwrap() <= Flat.part_two(ns).unwrap());
+        let error = format!("{:#}", Tree.parse(&input).unwrap_err());
+        assert!(error.starts_with("line 1: nested 100000 deep"), "{}", error);
+
+        let limit = format!(
+            "{}1{}",
+            "[".repeat(MAX_TREE_DEPTH),
+            ",1]".repeat(MAX_TREE_DEPTH)
+        );
+        let input = format!("{}\n{}", limit, limit);
+        let (flats, trees) = (Flat.parse(&input).unwrap(), Tree.parse(&input).unwrap());
+        assert_eq!(Flat.part_two(flats).unwrap(), Tree.part_two(trees).unwrap());
+    }
+
+    #[test]
+    fn too_few_numbers() {
+        for input in ["", "[1,2]"] {
+            assert!(Flat.part_two(Flat.parse(input).unwrap()).is_err());
+            assert!(Tree.part_two(Tree.parse(input).unwrap()).is_err());
+        }
+        assert!(Flat.part_one(Flat.parse("").unwrap()).is_err());
+        assert!(Tree.part_one(Tree.parse("").unwrap()).is_err());
+    }
+
+    fn unreduced(rng: &mut Rng, depth: usize) -> String {
+        if depth > 0 && (depth == 8 || rng.below(3) == 0) {
+            return rng.below(30).to_string();
+        }
+        format!(
+            "[{},{}]",
+            unreduced(rng, depth + 1),
+            unreduced(rng, depth + 1)
+        )
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |input: &str| format!("{}", parse_number(input).unwrap_err());
+        assert_eq!(
+            parse_number("[123,[4,56]]").unwrap().to_string(),
+            "[123,[4,56]]"
+        );
+        assert_eq!(error("7"), "column 1: expected '[', found '7'");
+        assert_eq!(error("[1;2]"), "column 3: expected ',', found ';'");
+        assert_eq!(
+            error("[[1,2],x]"),
+            "column 8: expected '[' or 0-9, found 'x'"
+        );
+        assert_eq!(error("[1,2"), "column 5: expected ']', found end of line");
+        assert_eq!(error("[1,2]]"), "column 6: expected end of line, found ']'");
+        assert_eq!(error("[1,"), "column 4: unexpected end of line");
+        assert_eq!(
+            error("[1,99999999999999999999]"),
+            "column 4: number too large"
+        );
+        assert_eq!(
+            format!("{:#}", parse("[1,2]\n[3,4\n").unwrap_err()),
+            "line 2: column 5: expected ']', found end of line"
+        );
+    }
+}
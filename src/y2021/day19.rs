@@ -0,0 +1,706 @@
+use std::cmp::{
+    Ordering,
+    Reverse,
+};
+use std::collections::VecDeque;
+
+use once_cell::sync::Lazy;
+
+use crate::export::Exporter;
+use crate::export::Format;
+use crate::generate::Generator;
+use crate::generate::Rng;
+use crate::params::Params;
+use crate::prelude::*;
+
+/// The number of beacons two scanners must have in common to be aligned with each other,
+/// unless set with `--param min-overlap=N`.
+const DEFAULT_OVERLAP: usize = 12;
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+struct Point(isize, isize, isize);
+
+impl Point {
+    fn sub(&self, other: &Point) -> Point {
+        Point(self.0 - other.0, self.1 - other.1, self.2 - other.2)
+    }
+
+    fn square_distance(&self, other: &Point) -> isize {
+        let dx = self.0 - other.0;
+        let dy = self.1 - other.1;
+        let dz = self.2 - other.2;
+        dx * dx + dy * dy + dz * dz
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Point(self.0 + other.0, self.1 + other.1, self.2 + other.2)
+    }
+
+    fn manhattan(&self, other: &Self) -> isize {
+        let dx = self.0 - other.0;
+        let dy = self.1 - other.1;
+        let dz = self.2 - other.2;
+        dx.abs() + dy.abs() + dz.abs()
+    }
+}
+
+/// Maps readings from one scanner's coordinates into another's: a rotation followed by a
+/// translation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Transform {
+    rotation: Rotation,
+    offset: Point,
+}
+
+impl Transform {
+    const IDENTITY: Transform = Transform {
+        rotation: Rotation::IDENTITY,
+        offset: Point(0, 0, 0),
+    };
+
+    /// The transform that applies `rotation` and then moves `from` onto `to`.
+    fn aligning(rotation: Rotation, from: Point, to: Point) -> Self {
+        Transform {
+            rotation,
+            offset: to.sub(&rotation.apply(from)),
+        }
+    }
+
+    fn apply(&self, p: &Point) -> Point {
+        self.rotation.apply(*p).add(&self.offset)
+    }
+
+    /// The transform that applies `other` and then `self`.
+    fn compose(&self, other: &Transform) -> Transform {
+        Transform {
+            rotation: self.rotation.compose(&other.rotation),
+            offset: self.apply(&other.offset),
+        }
+    }
+
+    fn inverse(&self) -> Transform {
+        let rotation = self.rotation.inverse();
+        Transform {
+            rotation,
+            offset: Point(0, 0, 0).sub(&rotation.apply(self.offset)),
+        }
+    }
+}
+
+/// One of the 24 ways a scanner can be turned while keeping its axes at right angles. Each is
+/// a matrix with a single 1 or -1 in every row and column, and a determinant of 1; those with a
+/// determinant of -1 would mirror the readings instead.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+struct Rotation([[isize; 3]; 3]);
+
+static ROTATIONS: Lazy<Vec<Rotation>> = Lazy::new(|| {
+    // Every rotation is some combination of quarter turns about the x and y axes.
+    let turns = [
+        Rotation([[1, 0, 0], [0, 0, -1], [0, 1, 0]]),
+        Rotation([[0, 0, 1], [0, 1, 0], [-1, 0, 0]]),
+    ];
+    let mut rotations = vec![Rotation::IDENTITY];
+    let mut i = 0;
+    while i < rotations.len() {
+        for turn in &turns {
+            let r = turn.compose(&rotations[i]);
+            if !rotations.contains(&r) {
+                rotations.push(r);
+            }
+        }
+        i += 1;
+    }
+    rotations
+});
+
+impl Rotation {
+    const IDENTITY: Rotation = Rotation([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+
+    fn all() -> &'static [Rotation] {
+        &ROTATIONS
+    }
+
+    fn apply(&self, p: Point) -> Point {
+        let [a, b, c] = self.0;
+        let row = |r: [isize; 3]| r[0] * p.0 + r[1] * p.1 + r[2] * p.2;
+        Point(row(a), row(b), row(c))
+    }
+
+    /// The rotation that applies `other` and then `self`.
+    fn compose(&self, other: &Rotation) -> Rotation {
+        let mut m = [[0; 3]; 3];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..3).map(|k| self.0[i][k] * other.0[k][j]).sum();
+            }
+        }
+        Rotation(m)
+    }
+
+    /// The rotation that undoes this one, which for a rotation matrix is its transpose.
+    fn inverse(&self) -> Rotation {
+        let mut m = [[0; 3]; 3];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = self.0[j][i];
+            }
+        }
+        Rotation(m)
+    }
+
+    #[cfg(test)]
+    fn det(&self) -> isize {
+        let m = &self.0;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+}
+
+fn solve(scans: Vec<Vec<Point>>, min_overlap: usize) -> Result<(HashSet<Point>, Vec<Point>)> {
+    let placements = place(&scans, min_overlap)?;
+    let positions = placements.iter().map(|t| t.offset).collect();
+    Ok((merge(&scans, &placements), positions))
+}
+
+/// Every beacon, in scanner 0's coordinates.
+fn merge(scans: &[Vec<Point>], placements: &[Transform]) -> HashSet<Point> {
+    scans
+        .iter()
+        .zip(placements)
+        .flat_map(|(scan, t)| scan.iter().map(move |p| t.apply(p)))
+        .collect()
+}
+
+/// Find the transform from each scanner's coordinates into scanner 0's, by aligning every pair
+/// of scanners that overlap and then chaining the transforms along the shortest path to
+/// scanner 0.
+fn place(scans: &[Vec<Point>], min_overlap: usize) -> Result<Vec<Transform>> {
+    if min_overlap < 3 {
+        return Err(anyhow!(
+            "an overlap of {} can't orient a scanner; at least 3 beacons are needed",
+            min_overlap
+        ));
+    }
+    let prints = scans
+        .iter()
+        .map(|s| Fingerprint::new(s))
+        .collect::<Vec<_>>();
+    // The scanners that overlap each scanner, with the transform from their coordinates into
+    // its own.
+    let mut neighbours = vec![Vec::new(); scans.len()];
+    for i in 0..scans.len() {
+        for j in i + 1..scans.len() {
+            if let Some(t) = align(&scans[i], &prints[i], &scans[j], &prints[j], min_overlap) {
+                neighbours[i].push((j, t));
+                neighbours[j].push((i, t.inverse()));
+            }
+        }
+    }
+
+    let mut placements = vec![None; scans.len()];
+    placements[0] = Some(Transform::IDENTITY);
+    let mut queue = VecDeque::from([0]);
+    while let Some(i) = queue.pop_front() {
+        let to_first = placements[i].expect("queued scanners are placed");
+        for &(j, to_i) in &neighbours[i] {
+            if placements[j].is_none() {
+                placements[j] = Some(to_first.compose(&to_i));
+                queue.push_back(j);
+            }
+        }
+    }
+    if placements.iter().any(Option::is_none) {
+        return Err(disconnected(&placements, &neighbours));
+    }
+    Ok(placements.into_iter().flatten().collect())
+}
+
+/// Describe the groups of scanners that couldn't be linked to scanner 0.
+fn disconnected(
+    placements: &[Option<Transform>],
+    neighbours: &[Vec<(usize, Transform)>],
+) -> anyhow::Error {
+    let mut seen = placements.iter().map(Option::is_some).collect::<Vec<_>>();
+    let mut groups = Vec::new();
+    for start in 0..seen.len() {
+        if seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut group = vec![start];
+        let mut i = 0;
+        while i < group.len() {
+            for &(j, _) in &neighbours[group[i]] {
+                if !seen[j] {
+                    seen[j] = true;
+                    group.push(j);
+                }
+            }
+            i += 1;
+        }
+        group.sort_unstable();
+        groups.push(format!("{:?}", group));
+    }
+    anyhow!(
+        "could not place every scanner relative to scanner 0; unconnected groups: {}",
+        groups.join(", ")
+    )
+}
+
+/// The squared distances between the beacons a scanner sees. These stay the same however the
+/// scanner is placed, so comparing them suggests which beacons two scanners have in common.
+#[derive(Debug, Clone)]
+struct Fingerprint {
+    /// The pairs of beacons (by index) at each distance.
+    pairs: HashMap<isize, Vec<(usize, usize)>>,
+    /// Every distance, sorted.
+    all: Vec<isize>,
+}
+
+impl Fingerprint {
+    fn new(points: &[Point]) -> Self {
+        let mut pairs = HashMap::<_, Vec<_>>::default();
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                let d = points[i].square_distance(&points[j]);
+                pairs.entry(d).or_default().push((i, j));
+            }
+        }
+        let mut all = pairs
+            .iter()
+            .flat_map(|(&d, p)| std::iter::repeat_n(d, p.len()))
+            .collect::<Vec<_>>();
+        all.sort_unstable();
+        Fingerprint { pairs, all }
+    }
+}
+
+/// The number of values two sorted lists have in common, counting repeats.
+fn shared(a: &[isize], b: &[isize]) -> usize {
+    let (mut i, mut j, mut count) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                count += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    count
+}
+
+/// Find the transform taking `source` readings into the coordinates of `dest`, if at least
+/// `min_overlap` beacons can be matched up.
+fn align(
+    dest: &[Point],
+    dest_print: &Fingerprint,
+    source: &[Point],
+    source_print: &Fingerprint,
+    min_overlap: usize,
+) -> Option<Transform> {
+    // Shared beacons are all the same distance apart in both scanners.
+    if shared(&dest_print.all, &source_print.all) < min_overlap * (min_overlap - 1) / 2 {
+        return None;
+    }
+    // Each pair of beacons the same distance apart in both scanners is a vote for its ends
+    // being the same beacons. A beacon seen by both collects a vote from each of the others
+    // it shares, while a wrong match rarely collects more than one or two.
+    let mut votes = vec![0; dest.len() * source.len()];
+    for (d, source_pairs) in &source_print.pairs {
+        let dest_pairs = match dest_print.pairs.get(d) {
+            Some(p) => p,
+            None => continue,
+        };
+        for &(a1, a2) in dest_pairs {
+            for &(b1, b2) in source_pairs {
+                for (a, b) in [(a1, b1), (a1, b2), (a2, b1), (a2, b2)] {
+                    votes[a * source.len() + b] += 1;
+                }
+            }
+        }
+    }
+    let mut candidates = (0..votes.len())
+        .filter(|&i| votes[i] + 1 >= min_overlap)
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|&i| Reverse(votes[i]));
+
+    let dest_set = dest.iter().collect::<HashSet<_>>();
+    for i in candidates {
+        let (a, b) = (i / source.len(), i % source.len());
+        for &rotation in Rotation::all() {
+            let transform = Transform::aligning(rotation, source[b], dest[a]);
+            let match_count = source
+                .iter()
+                .filter(|p| dest_set.contains(&transform.apply(p)))
+                .count();
+            if match_count >= min_overlap {
+                return Some(transform);
+            }
+        }
+    }
+    None
+}
+
+fn pairs<T>(slice: &[T]) -> impl Iterator<Item = (&T, &T)> {
+    slice
+        .iter()
+        .enumerate()
+        .flat_map(|(i, p)| slice[i + 1..].iter().map(move |q| (p, q)))
+}
+
+fn parse(input: &str) -> Vec<Vec<Point>> {
+    let mut lines = input.lines().peekable();
+    let mut scanners = Vec::new();
+    while lines.peek().is_some() {
+        let scanner = parse_scanner(&mut lines).unwrap();
+        scanners.push(scanner);
+    }
+    scanners
+}
+
+fn parse_scanner<'a, I>(mut lines: I) -> Option<Vec<Point>>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let header = lines.next()?;
+    if !header.starts_with("---") {
+        return None;
+    }
+    let mut points = Vec::new();
+    loop {
+        let line = match lines.next() {
+            Some("") | None => break,
+            Some(l) => l,
+        };
+        let mut split = line.split(',').filter_map(|s| s.parse::<isize>().ok());
+        let x = split.next().unwrap();
+        let y = split.next().unwrap();
+        let z = split.next().unwrap();
+        points.push(Point(x, y, z));
+    }
+    Some(points)
+}
+
+fn part_one(scans: Vec<Vec<Point>>, min_overlap: usize) -> Result<usize> {
+    Ok(solve(scans, min_overlap)?.0.len())
+}
+
+fn part_two(scans: Vec<Vec<Point>>, min_overlap: usize) -> Result<usize> {
+    let (_beacons, scanners) = solve(scans, min_overlap)?;
+    let furthest = pairs(&scanners)
+        .map(|(a, b)| a.manhattan(b) as usize)
+        .max()
+        .unwrap();
+    Ok(furthest)
+}
+
+/// The beacons and scanners in scanner 0's coordinates. A scanner's orientation is the
+/// rotation taking its readings onto scanner 0's axes; the PLY point cloud has no room for it,
+/// so there scanners are just red points among the white beacons.
+pub fn export(input: &str, format: Format, params: &Params) -> Result<String> {
+    let scans = parse(input);
+    let min_overlap = params.get("min-overlap")?.unwrap_or(DEFAULT_OVERLAP);
+    let placements = place(&scans, min_overlap)?;
+    let mut beacons = merge(&scans, &placements).into_iter().collect::<Vec<_>>();
+    beacons.sort_by_key(|p| (p.0, p.1, p.2));
+
+    let mut out = String::new();
+    match format {
+        Format::Csv => {
+            out.push_str("kind,index,x,y,z,r11,r12,r13,r21,r22,r23,r31,r32,r33\n");
+            for (i, t) in placements.iter().enumerate() {
+                let p = t.offset;
+                let cells = t.rotation.0.iter().flatten().map(|c| c.to_string());
+                let cells = cells.collect::<Vec<_>>().join(",");
+                out.push_str(&format!(
+                    "scanner,{},{},{},{},{}\n",
+                    i, p.0, p.1, p.2, cells
+                ));
+            }
+            for (i, p) in beacons.iter().enumerate() {
+                out.push_str(&format!("beacon,{},{},{},{},,,,,,,,,\n", i, p.0, p.1, p.2));
+            }
+        }
+        Format::Json => {
+            let scanners = placements
+                .iter()
+                .map(|t| {
+                    let p = t.offset;
+                    let [a, b, c] = t.rotation.0;
+                    format!(
+                        "    {{\"position\": [{}, {}, {}], \"orientation\": [{:?}, {:?}, {:?}]}}",
+                        p.0, p.1, p.2, a, b, c
+                    )
+                })
+                .collect::<Vec<_>>();
+            let beacons = beacons
+                .iter()
+                .map(|p| format!("    [{}, {}, {}]", p.0, p.1, p.2))
+                .collect::<Vec<_>>();
+            out.push_str(&format!(
+                "{{\n  \"scanners\": [\n{}\n  ],\n  \"beacons\": [\n{}\n  ]\n}}\n",
+                scanners.join(",\n"),
+                beacons.join(",\n")
+            ));
+        }
+        Format::Ply => {
+            out.push_str("ply\nformat ascii 1.0\n");
+            out.push_str("comment beacons are white and scanners red, ");
+            out.push_str("in scanner 0's coordinates\n");
+            out.push_str(&format!(
+                "element vertex {}\n",
+                placements.len() + beacons.len()
+            ));
+            for property in [
+                "int x",
+                "int y",
+                "int z",
+                "uchar red",
+                "uchar green",
+                "uchar blue",
+            ] {
+                out.push_str(&format!("property {}\n", property));
+            }
+            out.push_str("end_header\n");
+            for t in &placements {
+                let p = t.offset;
+                out.push_str(&format!("{} {} {} 255 0 0\n", p.0, p.1, p.2));
+            }
+            for p in &beacons {
+                out.push_str(&format!("{} {} {} 255 255 255\n", p.0, p.1, p.2));
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Random readings from `size` scanners. Each scanner is placed near the previous one, with
+/// enough beacons in their shared range for the two to be matched.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    generate_scanners(rng, size).2
+}
+
+/// Generate readings along with the true scanner positions and beacons they were made from.
+fn generate_scanners(rng: &mut Rng, size: usize) -> (Vec<Point>, HashSet<Point>, String) {
+    const RANGE: isize = 1000;
+
+    let random_point = |rng: &mut Rng, lo: &Point, hi: &Point| {
+        Point(
+            rng.range(lo.0, hi.0),
+            rng.range(lo.1, hi.1),
+            rng.range(lo.2, hi.2),
+        )
+    };
+    let mut scanners = Vec::<Point>::new();
+    let mut beacons = Vec::new();
+    for _ in 0..size {
+        let d = Point(RANGE / 2, RANGE / 2, RANGE / 2);
+        let scanner = match scanners.last() {
+            Some(prev) => random_point(rng, &prev.sub(&d), &prev.add(&d)),
+            None => Point(0, 0, 0),
+        };
+        let r = Point(RANGE, RANGE, RANGE);
+        let (lo, hi) = (scanner.sub(&r), scanner.add(&r));
+        if let Some(prev) = scanners.last() {
+            // Plant matching beacons where the two scanners' ranges overlap.
+            let (plo, phi) = (prev.sub(&r), prev.add(&r));
+            let shared_lo = Point(lo.0.max(plo.0), lo.1.max(plo.1), lo.2.max(plo.2));
+            let shared_hi = Point(hi.0.min(phi.0), hi.1.min(phi.1), hi.2.min(phi.2));
+            for _ in 0..DEFAULT_OVERLAP + rng.below(4) {
+                beacons.push(random_point(rng, &shared_lo, &shared_hi));
+            }
+        }
+        for _ in 0..rng.below(10) {
+            beacons.push(random_point(rng, &lo, &hi));
+        }
+        scanners.push(scanner);
+    }
+
+    let mut input = String::new();
+    for (i, scanner) in scanners.iter().enumerate() {
+        let rotation = Rotation::all()[rng.below(24)];
+        let mut seen = beacons
+            .iter()
+            .filter(|b| {
+                let d = b.sub(scanner);
+                d.0.abs() <= RANGE && d.1.abs() <= RANGE && d.2.abs() <= RANGE
+            })
+            .map(|b| rotation.apply(b.sub(scanner)))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        seen.sort_by_key(|p| (p.0, p.1, p.2));
+        rng.shuffle(&mut seen);
+        input.push_str(&format!("--- scanner {} ---\n", i));
+        for p in seen {
+            input.push_str(&format!("{},{},{}\n", p.0, p.1, p.2));
+        }
+        input.push('\n');
+    }
+    (scanners, beacons.into_iter().collect(), input)
+}
+
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 19,
+    title: "Beacon Scanner",
+    run,
+    generator: Some(Generator {
+        size: "number of scanners",
+        default_size: 30,
+        max_size: None,
+        generate,
+    }),
+    exporter: Some(Exporter {
+        what: "beacon map and scanner placements",
+        export,
+    }),
+};
+
+pub fn run(runner: &Runner) {
+    let min_overlap = runner.param("min-overlap").unwrap_or(DEFAULT_OVERLAP);
+    runner.run(
+        parse,
+        |scans| part_one(scans, min_overlap),
+        |scans| part_two(scans, min_overlap),
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generated() {
+        for seed in 0..3 {
+            let (scanners, beacons, input) = generate_scanners(&mut Rng::new(seed), 4);
+            let scans = parse(&input);
+            assert_eq!(
+                part_one(scans.clone(), DEFAULT_OVERLAP).unwrap(),
+                beacons.len(),
+                "seed {}",
+                seed
+            );
+            let furthest = pairs(&scanners)
+                .map(|(a, b)| a.manhattan(b) as usize)
+                .max()
+                .unwrap();
+            assert_eq!(
+                part_two(scans, DEFAULT_OVERLAP).unwrap(),
+                furthest,
+                "seed {}",
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn rotation_group() {
+        let all = Rotation::all();
+        assert_eq!(all.iter().collect::<HashSet<_>>().len(), 24);
+        assert!(all.iter().all(|r| r.det() == 1));
+        assert!(all.contains(&Rotation::IDENTITY));
+        for a in all {
+            assert_eq!(a.compose(&a.inverse()), Rotation::IDENTITY);
+            for b in all {
+                let ab = a.compose(b);
+                assert!(all.contains(&ab));
+                let p = Point(1, 2, 3);
+                assert_eq!(ab.apply(p), a.apply(b.apply(p)));
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_reflections() {
+        let (_, _, input) = generate_scanners(&mut Rng::new(11), 2);
+        let scans = parse(&input);
+        let (first, second) = (&scans[0], &scans[1]);
+        let print = Fingerprint::new(first);
+        assert!(align(first, &print, second, &Fingerprint::new(second), 12).is_some());
+        // A mirror image of the second scanner's readings can't be turned to match.
+        let mirrored = second
+            .iter()
+            .map(|p| Point(-p.0, p.1, p.2))
+            .collect::<Vec<_>>();
+        let mirrored_print = Fingerprint::new(&mirrored);
+        assert!(align(first, &print, &mirrored, &mirrored_print, 12).is_none());
+    }
+
+    #[test]
+    fn overlap_threshold() {
+        let (scanners, beacons, input) = generate_scanners(&mut Rng::new(5), 4);
+        let (found, positions) = solve(parse(&input), 6).unwrap();
+        assert_eq!(found.len(), beacons.len());
+        assert_eq!(positions.len(), scanners.len());
+
+        // Demanding more shared beacons than there are beacons leaves the scanners unplaced.
+        let params = Params::new(vec![format!("min-overlap={}", beacons.len() + 1)
+            .parse()
+            .unwrap()]);
+        assert!(export(&input, Format::Csv, &params).is_err());
+    }
+
+    #[test]
+    fn export_formats() {
+        let (scanners, beacons, input) = generate_scanners(&mut Rng::new(8), 3);
+        let (_, positions) = solve(parse(&input), DEFAULT_OVERLAP).unwrap();
+
+        let csv = export(&input, Format::Csv, &Params::default()).unwrap();
+        let rows = csv
+            .lines()
+            .skip(1)
+            .map(|l| l.split(',').collect::<Vec<_>>());
+        let (scanner_rows, beacon_rows) = rows.partition::<Vec<_>, _>(|r| r[0] == "scanner");
+        assert_eq!(beacon_rows.len(), beacons.len());
+        assert!(beacon_rows.iter().all(|r| r.len() == 14));
+        for (row, p) in scanner_rows.iter().zip(&positions) {
+            assert_eq!(row[2..5], [p.0, p.1, p.2].map(|c| c.to_string()));
+        }
+
+        let json = export(&input, Format::Json, &Params::default()).unwrap();
+        assert_eq!(json.matches("\"position\"").count(), scanners.len());
+        let ply = export(&input, Format::Ply, &Params::default()).unwrap();
+        let vertices = format!("element vertex {}\n", scanners.len() + beacons.len());
+        assert!(ply.contains(&vertices));
+        assert_eq!(
+            ply.lines().skip_while(|l| *l != "end_header").count(),
+            scanners.len() + beacons.len() + 1
+        );
+    }
+
+    #[test]
+    fn shared_counts_repeats() {
+        assert_eq!(shared(&[1, 2, 2, 2, 5], &[2, 2, 3, 5, 5]), 3);
+        assert_eq!(shared(&[], &[1]), 0);
+    }
+
+    #[test]
+    fn transform_algebra() {
+        let rotations = Rotation::all();
+        let a = Transform::aligning(rotations[5], Point(1, 2, 3), Point(-7, 0, 4));
+        let b = Transform::aligning(rotations[17], Point(40, -2, 9), Point(3, 3, 3));
+        let p = Point(11, -5, 8);
+        assert_eq!(a.compose(&b).apply(&p), a.apply(&b.apply(&p)));
+        assert_eq!(a.inverse().apply(&a.apply(&p)), p);
+        assert_eq!(a.compose(&a.inverse()), Transform::IDENTITY);
+        assert_eq!(a.apply(&Point(1, 2, 3)), Point(-7, 0, 4));
+    }
+
+    #[test]
+    fn disconnected_scanners() {
+        let (_, _, first) = generate_scanners(&mut Rng::new(1), 3);
+        let (_, _, second) = generate_scanners(&mut Rng::new(2), 2);
+        let err = solve(parse(&(first + &second)), DEFAULT_OVERLAP).unwrap_err();
+        assert!(
+            err.to_string().ends_with("unconnected groups: [3, 4]"),
+            "{}",
+            err
+        );
+        assert!(solve(parse(&second), 2).is_err());
+    }
+}
@@ -57,6 +57,15 @@ fn part_two(steps: Vec<Step>) -> usize {
     pos * depth
 }
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 2,
+    title: "Dive!",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
     runner.run(parse, part_one, part_two);
 }
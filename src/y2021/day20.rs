@@ -26,8 +26,7 @@ fn pad(image: &mut Vec<Vec<usize>>) {
         row.push(0);
         row.push(0);
     }
-    let mut blank_row = Vec::new();
-    blank_row.resize(width + 4, 0);
+    let blank_row = vec![0; width + 4];
     image.insert(0, blank_row.clone());
     image.insert(0, blank_row.clone());
     image.push(blank_row.clone());
@@ -54,6 +53,15 @@ fn part_two(input: (Vec<usize>, Vec<Vec<usize>>)) -> usize {
     enhance(image, &algorithm, 50)
 }
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 20,
+    title: "Trench Map",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
     runner.run(parse, part_one, part_two);
 }
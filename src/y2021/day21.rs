@@ -49,7 +49,7 @@ fn part_two(start: (usize, usize)) -> usize {
     let mut cache = HashMap::default();
 
     let (wins, losses) = ways(p1, 0, p2, 0, &mut cache);
-    wins.max(losses) as usize
+    wins.max(losses)
 }
 
 // Compute # of ways for player 1 to result in outcome `win`
@@ -85,6 +85,15 @@ fn ways(
     total
 }
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 21,
+    title: "Dirac Dice",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
     runner.run(parse, part_one, part_two);
 }
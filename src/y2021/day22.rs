@@ -0,0 +1,829 @@
+use crate::export::Exporter;
+use crate::export::Format;
+use crate::generate::Generator;
+use crate::generate::Rng;
+use crate::log::Log;
+use crate::params::Params;
+use crate::prelude::*;
+
+fn parse(input: &str) -> Result<Vec<(bool, Cube)>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_step(line.trim_end()).with_context(|| format!("line {}", i + 1)))
+        .collect()
+}
+
+fn parse_step(line: &str) -> Result<(bool, Cube)> {
+    let (state, cube) = line
+        .split_once(' ')
+        .ok_or_else(|| anyhow!("expected a state and a cuboid"))?;
+    let state = match state {
+        "on" => true,
+        "off" => false,
+        _ => return Err(anyhow!("bad state: {}", state)),
+    };
+    Ok((state, cube.parse()?))
+}
+
+fn parse_range(input: &str, prefix: &str) -> Result<Range> {
+    let (start, end) = input
+        .strip_prefix(prefix)
+        .and_then(|r| r.split_once(".."))
+        .ok_or_else(|| anyhow!("expected {}START..END, found {:?}", prefix, input))?;
+    let range = Range {
+        start: start.parse()?,
+        end: end.parse()?,
+    };
+    if range.start > range.end {
+        return Err(anyhow!("empty range {}", input));
+    }
+    Ok(range)
+}
+
+/// The region considered while initializing the reactor, unless `--param region=x=..,y=..,z=..`
+/// picks another.
+const INIT_REGION: Cube = Cube {
+    x: Range::new(-50, 50),
+    y: Range::new(-50, 50),
+    z: Range::new(-50, 50),
+};
+
+/// A way of counting the lit cubes after a sequence of steps.
+struct Engine {
+    name: &'static str,
+    solve: fn(Vec<(bool, Cube)>) -> Result<usize>,
+    /// The region counted by part one.
+    region: Cube,
+}
+
+impl Engine {
+    /// Count the cubes inside `region` that are lit after the steps.
+    fn lit_within(&self, steps: Vec<(bool, Cube)>, region: &Cube) -> Result<usize> {
+        let clipped = steps
+            .into_iter()
+            .filter_map(|(on, cube)| cube.intersect(region).map(|c| (on, c)))
+            .collect();
+        (self.solve)(clipped)
+    }
+}
+
+impl<'a> Solution<'a> for Engine {
+    type Input = Vec<(bool, Cube)>;
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn parse(&self, input: &'a str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(&self, steps: Self::Input) -> Result<usize> {
+        self.lit_within(steps, &self.region)
+    }
+
+    fn part_two(&self, steps: Self::Input) -> Result<usize> {
+        (self.solve)(steps)
+    }
+}
+
+const ENGINES: [Engine; 2] = [
+    Engine {
+        name: "default",
+        solve,
+        region: INIT_REGION,
+    },
+    Engine {
+        name: "split",
+        solve: solve_split,
+        region: INIT_REGION,
+    },
+];
+
+/// A random reboot sequence of `size` steps. Like the real inputs, the first fifth lie within
+/// the initialization region and the rest are much larger, though these overlap each other far
+/// more than in a real input to stress the solvers.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut steps = String::new();
+    for i in 0..size {
+        let (bound, extent) = if i < size.div_ceil(5) {
+            (50, 25)
+        } else {
+            (100_000, 150_000)
+        };
+        let mut range = || {
+            let start = rng.range(-bound, bound - 1);
+            let end = (start + rng.range(0, extent)).min(bound);
+            format!("{}..{}", start, end)
+        };
+        let (x, y, z) = (range(), range(), range());
+        let state = if i == 0 || rng.below(3) > 0 {
+            "on"
+        } else {
+            "off"
+        };
+        steps.push_str(&format!("{} x={},y={},z={}\n", state, x, y, z));
+    }
+    steps
+}
+
+/// The lit region after the reboot, or after its first `--param steps=N` steps, as disjoint
+/// cuboids with their bounds and the number of lit cubes in `--param region`.
+pub fn export(input: &str, format: Format, params: &Params) -> Result<String> {
+    let steps = parse(input)?;
+    let region = params.get("region")?.unwrap_or(INIT_REGION);
+    let count = params.get("steps")?.unwrap_or(steps.len());
+    if count > steps.len() {
+        return Err(anyhow!(
+            "only {} steps to apply, not {}",
+            steps.len(),
+            count
+        ));
+    }
+    let mut reactor = Reactor::default();
+    for &(on, cube) in &steps[..count] {
+        reactor.apply(on, cube);
+    }
+    let (lit, within) = (reactor.lit()?, reactor.lit_within(&region)?);
+    let cuboids = reactor.cuboids();
+
+    let mut out = String::new();
+    match format {
+        Format::Csv => {
+            out.push_str("kind,x0,x1,y0,y1,z0,z1,lit\n");
+            let row = |kind: &str, c: &Cube, lit: String| {
+                format!(
+                    "{},{},{},{},{},{},{},{}\n",
+                    kind, c.x.start, c.x.end, c.y.start, c.y.end, c.z.start, c.z.end, lit
+                )
+            };
+            out.push_str(&row("region", &region, within.to_string()));
+            if let Some(bounds) = reactor.bounds() {
+                out.push_str(&row("bounds", &bounds, lit.to_string()));
+            }
+            for c in cuboids {
+                out.push_str(&row("cuboid", c, c.volume()?.to_string()));
+            }
+        }
+        Format::Json => {
+            let ranges = |c: &Cube| {
+                format!(
+                    "[[{}, {}], [{}, {}], [{}, {}]]",
+                    c.x.start, c.x.end, c.y.start, c.y.end, c.z.start, c.z.end
+                )
+            };
+            let bounds = match reactor.bounds() {
+                Some(b) => ranges(&b),
+                None => "null".to_owned(),
+            };
+            let cuboids = cuboids
+                .iter()
+                .map(|c| format!("    {}", ranges(c)))
+                .collect::<Vec<_>>();
+            out.push_str(&format!(
+                "{{\n  \"steps\": {},\n  \"lit\": {},\n  \
+                 \"region\": {{\"ranges\": {}, \"lit\": {}}},\n  \
+                 \"bounds\": {},\n  \"cuboids\": [\n{}\n  ]\n}}\n",
+                count,
+                lit,
+                ranges(&region),
+                within,
+                bounds,
+                cuboids.join(",\n")
+            ));
+        }
+        Format::Ply => {
+            // Each cuboid is a closed box, with x=a..b spanning a to b + 1 so that a single cube
+            // has a volume.
+            out.push_str("ply\nformat ascii 1.0\n");
+            out.push_str("comment lit cuboids of the reactor\n");
+            out.push_str(&format!("element vertex {}\n", cuboids.len() * 8));
+            out.push_str("property int x\nproperty int y\nproperty int z\n");
+            out.push_str(&format!("element face {}\n", cuboids.len() * 6));
+            out.push_str("property list uchar int vertex_indices\nend_header\n");
+            for c in cuboids {
+                for i in 0..8 {
+                    let corner = |r: &Range, bit: usize| {
+                        if i & bit == 0 {
+                            r.start
+                        } else {
+                            r.end + 1
+                        }
+                    };
+                    let (x, y, z) = (corner(&c.x, 1), corner(&c.y, 2), corner(&c.z, 4));
+                    out.push_str(&format!("{} {} {}\n", x, y, z));
+                }
+            }
+            for i in 0..cuboids.len() {
+                let base = i * 8;
+                for face in [
+                    [0, 2, 3, 1],
+                    [4, 5, 7, 6],
+                    [0, 1, 5, 4],
+                    [2, 6, 7, 3],
+                    [0, 4, 6, 2],
+                    [1, 3, 7, 5],
+                ] {
+                    let [a, b, c, d] = face.map(|v| base + v);
+                    out.push_str(&format!("4 {} {} {} {}\n", a, b, c, d));
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 22,
+    title: "Reactor Reboot",
+    run,
+    generator: Some(Generator {
+        size: "number of cuboids",
+        default_size: 400,
+        max_size: None,
+        generate,
+    }),
+    exporter: Some(Exporter {
+        what: "lit cuboids of the rebooted reactor",
+        export,
+    }),
+};
+
+pub fn run(runner: &Runner) {
+    let region = runner.param("region").unwrap_or(INIT_REGION);
+    let point = runner.param("point");
+    if runner.explain() || point.is_some() {
+        // A bad input is reported when the engines parse it.
+        if let Ok(steps) = parse(runner.input()) {
+            if runner.explain() {
+                explain(&steps, &region, runner.log());
+            }
+            if let Some(point) = point {
+                watch(&steps, point, runner.log());
+            }
+        }
+    }
+    let [default, split] = ENGINES.map(|e| Engine { region, ..e });
+    runner.solve(&[&default, &split]);
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+struct Cube {
+    x: Range,
+    y: Range,
+    z: Range,
+}
+
+impl Cube {
+    fn intersect(&self, other: &Self) -> Option<Cube> {
+        intersect(&self.x, &other.x).and_then(|x| {
+            intersect(&self.y, &other.y)
+                .and_then(move |y| intersect(&self.z, &other.z).map(move |z| Cube { x, y, z }))
+        })
+    }
+
+    /// Push the parts of `self` outside of `other` to `out`, as at most six disjoint cuboids.
+    fn subtract(&self, other: &Self, out: &mut Vec<Cube>) {
+        let inter = match self.intersect(other) {
+            Some(inter) => inter,
+            None => {
+                out.push(*self);
+                return;
+            }
+        };
+        // Slice off the slabs on either side of the intersection along each axis in turn,
+        // narrowing what remains to the intersection's extent on that axis.
+        let mut rest = *self;
+        if rest.x.start < inter.x.start {
+            out.push(Cube {
+                x: Range::new(rest.x.start, inter.x.start - 1),
+                ..rest
+            });
+        }
+        if inter.x.end < rest.x.end {
+            out.push(Cube {
+                x: Range::new(inter.x.end + 1, rest.x.end),
+                ..rest
+            });
+        }
+        rest.x = inter.x;
+        if rest.y.start < inter.y.start {
+            out.push(Cube {
+                y: Range::new(rest.y.start, inter.y.start - 1),
+                ..rest
+            });
+        }
+        if inter.y.end < rest.y.end {
+            out.push(Cube {
+                y: Range::new(inter.y.end + 1, rest.y.end),
+                ..rest
+            });
+        }
+        rest.y = inter.y;
+        if rest.z.start < inter.z.start {
+            out.push(Cube {
+                z: Range::new(rest.z.start, inter.z.start - 1),
+                ..rest
+            });
+        }
+        if inter.z.end < rest.z.end {
+            out.push(Cube {
+                z: Range::new(inter.z.end + 1, rest.z.end),
+                ..rest
+            });
+        }
+    }
+
+    fn contains(&self, x: isize, y: isize, z: isize) -> bool {
+        self.x.contains(x) && self.y.contains(y) && self.z.contains(z)
+    }
+
+    fn volume(&self) -> Result<i128> {
+        self.x
+            .len()
+            .checked_mul(self.y.len())
+            .and_then(|v| v.checked_mul(self.z.len()))
+            .ok_or_else(|| anyhow!("volume of {} overflows", self))
+    }
+}
+
+impl std::fmt::Display for Cube {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "x={},y={},z={}", self.x, self.y, self.z)
+    }
+}
+
+impl std::str::FromStr for Cube {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split(',').collect::<Vec<_>>()[..] {
+            [x, y, z] => Ok(Self {
+                x: parse_range(x, "x=")?,
+                y: parse_range(y, "y=")?,
+                z: parse_range(z, "z=")?,
+            }),
+            _ => Err(anyhow!("expected x=..,y=..,z=.., found {:?}", s)),
+        }
+    }
+}
+
+/// A single cube, written `x,y,z`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Point(isize, isize, isize);
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.0, self.1, self.2)
+    }
+}
+
+impl std::str::FromStr for Point {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match parse_split::<isize>(s, ',')?[..] {
+            [x, y, z] => Ok(Point(x, y, z)),
+            _ => Err(anyhow!("expected x,y,z")),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+struct Range {
+    start: isize,
+    end: isize,
+}
+
+impl Range {
+    const fn new(start: isize, end: isize) -> Self {
+        Range { start, end }
+    }
+
+    fn len(&self) -> i128 {
+        self.end as i128 - self.start as i128 + 1
+    }
+
+    fn contains(&self, p: isize) -> bool {
+        self.start <= p && p <= self.end
+    }
+}
+
+impl std::fmt::Display for Range {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+fn intersect(a: &Range, b: &Range) -> Option<Range> {
+    // a leads
+    // a0              a1
+    // <---------------->
+    //        <-------|-------->
+    //        b0      b1  or  b1
+    if a.contains(b.start) {
+        let end = b.end.min(a.end);
+        return Some(Range {
+            start: b.start,
+            end,
+        });
+    }
+    // b leads
+    //        <-------|-------->
+    //        a0      a1  or  a1
+    // <---------------->
+    // b0              b1
+    if b.contains(a.start) {
+        let end = b.end.min(a.end);
+        return Some(Range {
+            start: a.start,
+            end,
+        });
+    }
+    None
+}
+
+fn solve(cubes: Vec<(bool, Cube)>) -> Result<usize> {
+    let mut weights = HashMap::<Cube, isize>::default();
+
+    // for each new cuboid
+    for (on, cube) in cubes {
+        let intersections: Option<Vec<(Cube, isize)>> = {
+            let inter = weights
+                .iter()
+                .flat_map(|(s, w)| s.intersect(&cube).map(|inter| (inter, *w)))
+                .collect::<Vec<_>>();
+            if inter.is_empty() {
+                None
+            } else {
+                Some(inter)
+            }
+        };
+        if on {
+            *weights.entry(cube).or_insert(0) += 1;
+        }
+        for (intersection, weight) in intersections.iter().flat_map(|i| i.iter()) {
+            let entry = weights.entry(*intersection).or_insert(0);
+            *entry -= weight;
+            if *entry == 0 {
+                weights.remove(intersection);
+            }
+        }
+    }
+    let mut total = 0i128;
+    for (cube, &weight) in &weights {
+        total = cube
+            .volume()?
+            .checked_mul(weight as i128)
+            .and_then(|v| total.checked_add(v))
+            .ok_or_else(|| anyhow!("total volume overflows"))?;
+    }
+    lit_count(total)
+}
+
+/// Convert a total volume to a count of cubes. A negative total means the weights were
+/// miscounted, so it is an error rather than being allowed to wrap.
+fn lit_count(total: i128) -> Result<usize> {
+    if total < 0 {
+        return Err(anyhow!("total lit volume is negative: {}", total));
+    }
+    usize::try_from(total).map_err(|_| anyhow!("too many lit cubes to count: {}", total))
+}
+
+/// Keep the lit region as a list of disjoint cuboids, carving each step's cuboid out of them
+/// and adding it back if it turns cubes on.
+fn solve_split(cubes: Vec<(bool, Cube)>) -> Result<usize> {
+    let mut reactor = Reactor::default();
+    for (on, cube) in cubes {
+        reactor.apply(on, cube);
+    }
+    reactor.lit()
+}
+
+/// The lit region of the reactor, as disjoint cuboids.
+#[derive(Debug, Clone, Default)]
+struct Reactor {
+    lit: Vec<Cube>,
+}
+
+impl Reactor {
+    fn apply(&mut self, on: bool, cube: Cube) {
+        let mut next = Vec::with_capacity(self.lit.len());
+        for c in &self.lit {
+            c.subtract(&cube, &mut next);
+        }
+        if on {
+            next.push(cube);
+        }
+        self.lit = next;
+    }
+
+    fn is_on(&self, x: isize, y: isize, z: isize) -> bool {
+        self.lit.iter().any(|c| c.contains(x, y, z))
+    }
+
+    /// The number of lit cubes.
+    fn lit(&self) -> Result<usize> {
+        self.lit_in(self.lit.iter().copied())
+    }
+
+    /// The number of lit cubes inside `region`.
+    fn lit_within(&self, region: &Cube) -> Result<usize> {
+        self.lit_in(self.lit.iter().filter_map(|c| c.intersect(region)))
+    }
+
+    fn lit_in<I: Iterator<Item = Cube>>(&self, cubes: I) -> Result<usize> {
+        let mut total = 0i128;
+        for cube in cubes {
+            total = total
+                .checked_add(cube.volume()?)
+                .ok_or_else(|| anyhow!("total volume overflows"))?;
+        }
+        lit_count(total)
+    }
+
+    /// The smallest cuboid containing every lit cube, if any are lit.
+    fn bounds(&self) -> Option<Cube> {
+        let span = |r: fn(&Cube) -> Range| {
+            let start = self.lit.iter().map(|c| r(c).start).min()?;
+            let end = self.lit.iter().map(|c| r(c).end).max()?;
+            Some(Range::new(start, end))
+        };
+        Some(Cube {
+            x: span(|c| c.x)?,
+            y: span(|c| c.y)?,
+            z: span(|c| c.z)?,
+        })
+    }
+
+    /// Disjoint cuboids that together make up the lit region.
+    fn cuboids(&self) -> &[Cube] {
+        &self.lit
+    }
+}
+
+/// Log the state of the reactor after each step.
+fn explain(steps: &[(bool, Cube)], region: &Cube, log: Log) {
+    let mut reactor = Reactor::default();
+    for (i, &(on, cube)) in steps.iter().enumerate() {
+        reactor.apply(on, cube);
+        let lit = match (reactor.lit(), reactor.lit_within(region)) {
+            (Ok(all), Ok(within)) => format!("{} lit ({} in {})", all, within, region),
+            (Err(e), _) | (_, Err(e)) => e.to_string(),
+        };
+        let bounds = match reactor.bounds() {
+            Some(b) => b.to_string(),
+            None => "none".to_owned(),
+        };
+        log.info(format_args!(
+            "{:>3}. {:<3} {}: {} in {} cuboids, bounds {}",
+            i + 1,
+            if on { "on" } else { "off" },
+            cube,
+            lit,
+            reactor.cuboids().len(),
+            bounds
+        ));
+    }
+}
+
+/// Log each step that covers `point` and whether the point is lit after it, for following a
+/// single cube through the reboot with `--param point=x,y,z`.
+fn watch(steps: &[(bool, Cube)], point: Point, log: Log) {
+    let Point(x, y, z) = point;
+    let mut reactor = Reactor::default();
+    for (i, &(on, cube)) in steps.iter().enumerate() {
+        reactor.apply(on, cube);
+        if cube.contains(x, y, z) {
+            log.info(format_args!(
+                "{:>3}. {:<3} {}: {} is {}",
+                i + 1,
+                if on { "on" } else { "off" },
+                cube,
+                point,
+                if reactor.is_on(x, y, z) { "on" } else { "off" }
+            ));
+        }
+    }
+    log.info(format_args!(
+        "{} is {} after all {} steps",
+        point,
+        if reactor.is_on(x, y, z) { "on" } else { "off" },
+        steps.len()
+    ));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Count the lit cubes in `region` one at a time.
+    fn count_lit(steps: &[(bool, Cube)], region: &Cube) -> usize {
+        let mut lit = 0;
+        for x in region.x.start..=region.x.end {
+            for y in region.y.start..=region.y.end {
+                for z in region.z.start..=region.z.end {
+                    let last = steps.iter().rev().find(|(_, c)| c.contains(x, y, z));
+                    if let Some((true, _)) = last {
+                        lit += 1;
+                    }
+                }
+            }
+        }
+        lit
+    }
+
+    const INPUT: &str = "\
+on x=10..12,y=10..12,z=10..12
+on x=11..13,y=11..13,z=11..13
+off x=9..11,y=9..11,z=9..11
+on x=10..10,y=10..10,z=10..10";
+
+    #[test]
+    fn example() {
+        for engine in &ENGINES {
+            assert_eq!(
+                engine.part_one(parse(INPUT).unwrap()).unwrap(),
+                39,
+                "{}",
+                engine.name
+            );
+        }
+    }
+
+    #[test]
+    fn engines_agree() {
+        for seed in 0..5 {
+            let steps = parse(&generate(&mut Rng::new(seed), 60)).unwrap();
+            assert_eq!(
+                solve(steps.clone()).unwrap(),
+                solve_split(steps).unwrap(),
+                "seed {}",
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn lit_within() {
+        let regions = [INIT_REGION, "x=-20..10,y=0..30,z=-45..-5".parse().unwrap()];
+        for seed in 0..3 {
+            let steps = parse(&generate(&mut Rng::new(seed), 20)).unwrap();
+            for region in &regions {
+                let expected = count_lit(&steps, region);
+                for engine in &ENGINES {
+                    let lit = engine.lit_within(steps.clone(), region).unwrap();
+                    assert_eq!(
+                        lit, expected,
+                        "seed {}, {} in {}",
+                        seed, engine.name, region
+                    );
+                    let engine = Engine {
+                        region: *region,
+                        ..*engine
+                    };
+                    assert_eq!(engine.part_one(steps.clone()).unwrap(), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn overflow() {
+        let huge = Cube {
+            x: Range::new(isize::MIN, isize::MAX),
+            y: Range::new(isize::MIN, isize::MAX),
+            z: Range::new(0, 1),
+        };
+        assert!(huge.volume().is_err());
+        for engine in &ENGINES {
+            assert!(
+                (engine.solve)(vec![(true, huge)]).is_err(),
+                "{}",
+                engine.name
+            );
+        }
+        assert!(lit_count(-1).is_err());
+        assert_eq!(lit_count(27).unwrap(), 27);
+    }
+
+    fn cube(s: &str) -> Cube {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn intersect_edges() {
+        let a = cube("x=0..2,y=0..2,z=0..2");
+        // Sharing a single face.
+        assert_eq!(
+            a.intersect(&cube("x=2..4,y=0..2,z=0..2")),
+            Some(cube("x=2..2,y=0..2,z=0..2"))
+        );
+        // Adjacent but not overlapping.
+        assert_eq!(a.intersect(&cube("x=3..4,y=0..2,z=0..2")), None);
+        // Overlapping on two axes only.
+        assert_eq!(a.intersect(&cube("x=1..1,y=1..1,z=5..6")), None);
+        // Containment, in either order.
+        let inner = cube("x=1..1,y=0..1,z=1..2");
+        assert_eq!(a.intersect(&inner), Some(inner));
+        assert_eq!(inner.intersect(&a), Some(inner));
+        assert_eq!(a.intersect(&a), Some(a));
+        // Meeting at a single corner.
+        assert_eq!(
+            a.intersect(&cube("x=2..5,y=-3..0,z=2..2")),
+            Some(cube("x=2..2,y=0..0,z=2..2"))
+        );
+    }
+
+    #[test]
+    fn reactor_queries() {
+        let mut reactor = Reactor::default();
+        assert_eq!(reactor.bounds(), None);
+        for (on, c) in parse(INPUT).unwrap() {
+            reactor.apply(on, c);
+        }
+        assert_eq!(reactor.lit().unwrap(), 39);
+        assert!(reactor.is_on(10, 10, 10));
+        assert!(!reactor.is_on(11, 11, 11));
+        assert!(reactor.is_on(13, 13, 13));
+        assert_eq!(reactor.bounds(), Some(cube("x=10..13,y=10..13,z=10..13")));
+        assert_eq!(
+            reactor
+                .lit_within(&cube("x=12..20,y=12..20,z=12..20"))
+                .unwrap(),
+            8
+        );
+
+        let cuboids = reactor.cuboids();
+        for (i, a) in cuboids.iter().enumerate() {
+            for b in &cuboids[i + 1..] {
+                assert_eq!(a.intersect(b), None, "{} overlaps {}", a, b);
+            }
+        }
+        for x in 8..=14 {
+            for y in 8..=14 {
+                for z in 8..=14 {
+                    let in_cuboid = cuboids.iter().any(|c| c.contains(x, y, z));
+                    assert_eq!(in_cuboid, reactor.is_on(x, y, z));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn export_formats() {
+        let params = |ps: &[&str]| Params::new(ps.iter().map(|p| p.parse().unwrap()).collect());
+        let region = "region=x=12..20,y=12..20,z=12..20";
+        let csv = export(INPUT, Format::Csv, &params(&[region])).unwrap();
+        let rows = csv.lines().skip(1).collect::<Vec<_>>();
+        assert_eq!(rows[0], "region,12,20,12,20,12,20,8");
+        assert_eq!(rows[1], "bounds,10,13,10,13,10,13,39");
+        let volumes = rows[2..].iter().map(|r| r.rsplit(',').next().unwrap());
+        assert_eq!(
+            volumes.map(|v| v.parse::<usize>().unwrap()).sum::<usize>(),
+            39
+        );
+
+        let json = export(INPUT, Format::Json, &params(&["steps=1"])).unwrap();
+        assert!(json.contains("\"lit\": 27,"));
+        assert!(json.contains("\"bounds\": [[10, 12], [10, 12], [10, 12]]"));
+        let empty = export(INPUT, Format::Json, &params(&["steps=0"])).unwrap();
+        assert!(empty.contains("\"bounds\": null"));
+        assert!(export(INPUT, Format::Json, &params(&["steps=5"])).is_err());
+
+        let ply = export(INPUT, Format::Ply, &params(&["steps=1"])).unwrap();
+        assert!(ply.contains("element vertex 8\n") && ply.contains("element face 6\n"));
+        assert!(ply.contains("\n13 13 13\n"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |input: &str| format!("{:#}", parse(input).unwrap_err());
+        assert_eq!(
+            error("on x=1..2,y=1..2,z=1..2\nof x=1..2,y=1..2,z=1..2"),
+            "line 2: bad state: of"
+        );
+        assert_eq!(
+            error("on x=1..2,y=1..2"),
+            "line 1: expected x=..,y=..,z=.., found \"x=1..2,y=1..2\""
+        );
+        assert_eq!(
+            error("on x=1..2,y=1..2,z=3"),
+            "line 1: expected z=START..END, found \"z=3\""
+        );
+        assert_eq!(
+            error("off x=2..1,y=1..2,z=1..2"),
+            "line 1: empty range x=2..1"
+        );
+        assert!(parse("on x=1..2,y=1..2,z=1..a").is_err());
+    }
+
+    #[test]
+    fn parse_point() {
+        assert_eq!("1,-2,3".parse::<Point>().unwrap(), Point(1, -2, 3));
+        assert!("1,2".parse::<Point>().is_err());
+        assert!("1,2,x".parse::<Point>().is_err());
+    }
+}
@@ -0,0 +1,602 @@
+use std::fmt::Display;
+
+use crate::log::Log;
+use crate::prelude::*;
+use crate::search::SearchQueue;
+
+//    1 2 3 4
+//  01 2 3 4 56
+// #############
+// #01.3.5.7.9.#
+// ###B#C#B#D###
+//   #A#D#C#A#
+//   #########
+//
+// 0 - 10
+// rooms: 2 4 6 8
+//
+// The hall length, the number of rooms, their depth and where their doors open onto the hall
+// are all read from the diagram, so any burrow of this shape can be solved.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Frog(usize);
+
+impl Frog {
+    fn new(c: char) -> Result<Self> {
+        if !c.is_ascii_uppercase() {
+            return Err(anyhow!("bad char {}", c));
+        }
+        let i = (c as usize) - ('A' as usize);
+        Ok(Frog(i))
+    }
+
+    fn cost(&self) -> usize {
+        10usize.pow(self.0 as u32)
+    }
+
+    fn as_char(&self) -> char {
+        (b'A' + self.0 as u8) as char
+    }
+}
+
+/// The fixed shape of the burrow.
+#[derive(Debug, Clone)]
+struct Layout {
+    /// The hall position directly above each room.
+    doors: Vec<usize>,
+    depth: usize,
+}
+
+impl Layout {
+    fn is_door(&self, x: usize) -> bool {
+        self.doors.contains(&x)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Burrow {
+    layout: Layout,
+    start: SearchState,
+}
+
+impl Burrow {
+    /// Unfold the diagram for part two by inserting the two hidden rows under the top row of
+    /// each room. The hidden rows only fit the puzzle's shape: four full rooms two deep.
+    fn unfold(&mut self) -> Result<()> {
+        let insertions = [['D', 'D'], ['B', 'C'], ['A', 'B'], ['C', 'A']];
+        let full = self
+            .start
+            .rooms
+            .iter()
+            .all(|r| r.occupants.len() == self.layout.depth);
+        if self.start.rooms.len() != insertions.len() || self.layout.depth != 2 || !full {
+            return Err(anyhow!(
+                "part two unfolds the puzzle's burrow of {} full rooms two deep, but this one \
+                 has {} rooms {} deep{}",
+                insertions.len(),
+                self.start.rooms.len(),
+                self.layout.depth,
+                if full { "" } else { " and is not full" }
+            ));
+        }
+        for (room, insert) in self.start.rooms.iter_mut().zip(insertions) {
+            let top = room.occupants.pop().unwrap();
+            room.occupants
+                .extend(insert.iter().map(|c| Frog::new(*c).unwrap()));
+            room.occupants.push(top);
+        }
+        self.layout.depth += 2;
+        Ok(())
+    }
+}
+
+fn parse(input: &str) -> Result<Burrow> {
+    let mut lines = input.lines().skip(1);
+    let hall_line = lines.next().ok_or_else(|| anyhow!("missing hall"))?;
+    let hall_start = hall_line
+        .find(|c| c != '#' && c != ' ')
+        .ok_or_else(|| anyhow!("missing hall"))?;
+    let hall = hall_line[hall_start..]
+        .chars()
+        .take_while(|c| *c != '#')
+        .map(|c| match c {
+            '.' => Ok(None),
+            c => Frog::new(c).map(Some),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let hall_len = hall.len();
+
+    // Each remaining line with an open space or an amphipod is one row of the rooms, top first.
+    let rows = lines
+        .take_while(|l| l.chars().any(|c| c == '.' || c.is_alphabetic()))
+        .collect::<Vec<_>>();
+    let first_row = rows.first().ok_or_else(|| anyhow!("missing rooms"))?;
+    let columns = first_row
+        .char_indices()
+        .filter(|(_, c)| *c == '.' || c.is_alphabetic())
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+
+    let mut doors = Vec::with_capacity(columns.len());
+    for &col in &columns {
+        if col < hall_start || col >= hall_start + hall_len {
+            return Err(anyhow!(
+                "room at column {} does not open onto the hall",
+                col
+            ));
+        }
+        doors.push(col - hall_start);
+    }
+
+    let mut rooms = Vec::with_capacity(columns.len());
+    for &col in &columns {
+        let cells = rows
+            .iter()
+            .map(|row| row[col..].chars().next())
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| anyhow!("room at column {} is cut short", col))?;
+        // Rooms fill from the bottom, so open spaces may only appear above the amphipods.
+        let open = cells.iter().take_while(|c| **c == '.').count();
+        let mut room = Room::default();
+        for &c in cells[open..].iter().rev() {
+            if c == '.' {
+                return Err(anyhow!("room at column {} has a gap", col));
+            }
+            room.add(Frog::new(c)?);
+        }
+        rooms.push(room);
+    }
+    // Each kind of amphipod uses ten times the energy of the one before it, which has to fit.
+    if 10usize
+        .checked_pow(rooms.len().saturating_sub(1) as u32)
+        .is_none()
+    {
+        return Err(anyhow!(
+            "too many rooms: {} kinds of amphipod would use more energy than can be counted",
+            rooms.len()
+        ));
+    }
+    let frogs = rooms
+        .iter()
+        .flat_map(|r| r.occupants.iter())
+        .chain(hall.iter().flatten());
+    for f in frogs {
+        if f.0 >= rooms.len() {
+            return Err(anyhow!("no room for amphipod {}", f.as_char()));
+        }
+    }
+
+    Ok(Burrow {
+        layout: Layout {
+            doors,
+            depth: rows.len(),
+        },
+        start: SearchState { rooms, hall },
+    })
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+struct Room {
+    occupants: Vec<Frog>,
+}
+
+impl Room {
+    fn add(&mut self, frog: Frog) {
+        self.occupants.push(frog);
+    }
+
+    fn len(&self) -> usize {
+        self.occupants.len()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct SearchState {
+    rooms: Vec<Room>,
+    hall: Vec<Option<Frog>>,
+}
+
+/// A location in the burrow. Rooms are indexed by the amphipod that belongs in them and
+/// slots within a room count from the bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Location {
+    Hall(usize),
+    Room(usize, usize),
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            Location::Hall(x) => write!(f, "hall {}", x),
+            Location::Room(i, y) => write!(f, "room {} slot {}", Frog(i).as_char(), y),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Move {
+    frog: Frog,
+    from: Location,
+    to: Location,
+    energy: usize,
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {} ({} energy)",
+            self.frog.as_char(),
+            self.from,
+            self.to,
+            self.energy
+        )
+    }
+}
+
+impl SearchState {
+    fn apply(&mut self, m: &Move) {
+        match m.from {
+            Location::Hall(x) => self.hall[x] = None,
+            Location::Room(i, _) => {
+                self.rooms[i].occupants.pop();
+            }
+        }
+        match m.to {
+            Location::Hall(x) => self.hall[x] = Some(m.frog),
+            Location::Room(i, _) => self.rooms[i].add(m.frog),
+        }
+    }
+}
+
+fn part_one(burrow: Burrow, log: Log, explain: bool) -> Result<usize> {
+    solve_explained(burrow, log, explain)
+}
+
+/// Solve for the minimum energy, logging every move along the way if `explain` is set.
+fn solve_explained(burrow: Burrow, log: Log, explain: bool) -> Result<usize> {
+    let (cost, moves) =
+        solve(&burrow, log).ok_or_else(|| anyhow!("no way to organize the amphipods"))?;
+    if explain {
+        explain_path(&burrow, &moves, log);
+    }
+    Ok(cost)
+}
+
+fn solve(burrow: &Burrow, log: Log) -> Option<(usize, Vec<Move>)> {
+    let layout = &burrow.layout;
+    let hall_len = burrow.start.hall.len();
+    log.debug(Picture(layout, &burrow.start));
+    let mut fringe = SearchQueue::new();
+    fringe.push(0, (burrow.start.clone(), None));
+
+    // The move that led to each expanded state along with the index of the state it was made
+    // from, so the path can be replayed once the goal is reached.
+    let mut parents: Vec<Option<(usize, Move)>> = Vec::new();
+    let mut seen = HashSet::default();
+    while let Some((cost, (state, parent))) = fringe.pop() {
+        if seen.contains(&state) {
+            continue;
+        }
+        seen.insert(state.clone());
+        let idx = parents.len();
+        if let Some((_, m)) = parent {
+            log.trace(format_args!("{:>6}. cost {}: {}", idx, cost, m));
+        }
+        parents.push(parent);
+        if state.hall.iter().all(|s| s.is_none())
+            && state
+                .rooms
+                .iter()
+                .enumerate()
+                .all(|(i, r)| r.occupants.iter().all(|f| f.0 == i))
+        {
+            log.debug(Picture(layout, &state));
+            return Some((cost, path(&parents, idx)));
+        }
+        // - move a frog from their origin room to the hall
+        for (i, room) in state.rooms.iter().enumerate() {
+            if room.occupants.iter().all(|f| f.0 == i) {
+                // Don't need to move anything out of this room
+                continue;
+            }
+            let room_x = layout.doors[i];
+            // try moving the top-most frog in this room into the hall
+            let mut x_start = room_x;
+            while x_start >= 1 && state.hall[x_start - 1].is_none() {
+                x_start -= 1;
+            }
+            let mut x_end = room_x;
+            while x_end + 1 < hall_len && state.hall[x_end + 1].is_none() {
+                x_end += 1;
+            }
+            let mut new_rooms = state.rooms.clone();
+            let frog = new_rooms[i].occupants.pop().unwrap();
+            let y_cost = layout.depth - new_rooms[i].len();
+
+            let x_iter = (x_start..room_x)
+                .chain(room_x + 1..=x_end)
+                .filter(|&x| !layout.is_door(x));
+            for x in x_iter {
+                let x_cost = x.abs_diff(room_x);
+                let mut new_hall = state.hall.clone();
+                new_hall[x] = Some(frog);
+                let m = Move {
+                    frog,
+                    from: Location::Room(i, new_rooms[i].len()),
+                    to: Location::Hall(x),
+                    energy: (x_cost + y_cost) * frog.cost(),
+                };
+                fringe.push(
+                    cost + m.energy,
+                    (
+                        SearchState {
+                            rooms: new_rooms.clone(),
+                            hall: new_hall,
+                        },
+                        Some((idx, m)),
+                    ),
+                );
+            }
+        }
+        // - move a frog from the hall to their final position
+        for (x_pos, frog) in state.hall.iter().enumerate() {
+            let frog = match frog {
+                Some(f) => f,
+                None => continue,
+            };
+            if state.rooms[frog.0].occupants.iter().any(|of| of != frog) {
+                // The destination room is unavailable
+                continue;
+            }
+            let room_x = layout.doors[frog.0];
+            let (mut x_range, x_cost) = if room_x < x_pos {
+                (room_x..x_pos, x_pos - room_x)
+            } else {
+                (x_pos + 1..room_x, room_x - x_pos)
+            };
+            if x_range.any(|x| state.hall[x].is_some()) {
+                // Can move into destination but there is something in the way
+                continue;
+            }
+            let mut new_hall = state.hall.clone();
+            new_hall[x_pos] = None;
+            let mut new_rooms = state.rooms.clone();
+            let y_cost = layout.depth - new_rooms[frog.0].len();
+            let m = Move {
+                frog: *frog,
+                from: Location::Hall(x_pos),
+                to: Location::Room(frog.0, new_rooms[frog.0].len()),
+                energy: (x_cost + y_cost) * frog.cost(),
+            };
+            new_rooms[frog.0].add(*frog);
+            fringe.push(
+                cost + m.energy,
+                (
+                    SearchState {
+                        rooms: new_rooms,
+                        hall: new_hall,
+                    },
+                    Some((idx, m)),
+                ),
+            );
+        }
+    }
+    None
+}
+
+/// Follow the parent links back from `idx` to the start, returning the moves in order.
+fn path(parents: &[Option<(usize, Move)>], mut idx: usize) -> Vec<Move> {
+    let mut moves = Vec::new();
+    while let Some((parent, m)) = parents[idx] {
+        moves.push(m);
+        idx = parent;
+    }
+    moves.reverse();
+    moves
+}
+
+fn explain_path(burrow: &Burrow, moves: &[Move], log: Log) {
+    let mut state = burrow.start.clone();
+    let mut total = 0;
+    for (i, m) in moves.iter().enumerate() {
+        state.apply(m);
+        total += m.energy;
+        log.info(format_args!("{:>2}. {}, {} total", i + 1, m, total));
+        log.info(format_args!("{}\n", Picture(&burrow.layout, &state)));
+    }
+}
+
+/// Renders the burrow in the same format as the puzzle input.
+struct Picture<'a>(&'a Layout, &'a SearchState);
+
+impl Display for Picture<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let Picture(layout, state) = *self;
+        let width = state.hall.len() + 2;
+        writeln!(f, "{}", "#".repeat(width))?;
+        let mut hall_str = String::new();
+        hall_str.push('#');
+        for h in &state.hall {
+            hall_str.push(h.map(|a| a.as_char()).unwrap_or('.'));
+        }
+        hall_str.push('#');
+        writeln!(f, "{}", hall_str)?;
+
+        // Rows below the top one only have walls around the rooms themselves.
+        let first = layout.doors.iter().min().copied().unwrap_or(0);
+        let last = layout.doors.iter().max().copied().unwrap_or(0);
+        let mut line = vec![' '; width];
+        for i in (0..layout.depth).rev() {
+            for (x, c) in line.iter_mut().enumerate() {
+                *c = if i == layout.depth - 1 || (first..=last + 2).contains(&x) {
+                    '#'
+                } else {
+                    ' '
+                };
+            }
+            for (room, door) in state.rooms.iter().zip(&layout.doors) {
+                line[door + 1] = room.occupants.get(i).map(|a| a.as_char()).unwrap_or('.');
+            }
+            writeln!(f, "{}", line.iter().collect::<String>())?;
+        }
+        for (x, c) in line.iter_mut().enumerate() {
+            *c = if (first..=last + 2).contains(&x) {
+                '#'
+            } else {
+                ' '
+            };
+        }
+        write!(f, "{}", line.iter().collect::<String>().trim_end())
+    }
+}
+
+fn part_two(mut burrow: Burrow, log: Log, explain: bool) -> Result<usize> {
+    burrow.unfold()?;
+    solve_explained(burrow, log, explain)
+}
+
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 23,
+    title: "Amphipod",
+    run,
+    generator: None,
+    exporter: None,
+};
+
+pub fn run(runner: &Runner) {
+    let search = Search {
+        log: runner.log(),
+        explain: runner.explain(),
+    };
+    runner.solve(&[&search]);
+}
+
+/// Searches for the cheapest way to organize the burrow. Parsing goes through `Solution` so a
+/// malformed diagram is reported as a failure of both parts.
+struct Search {
+    log: Log,
+    explain: bool,
+}
+
+impl<'a> Solution<'a> for Search {
+    type Input = Burrow;
+
+    fn parse(&self, input: &'a str) -> Result<Burrow> {
+        parse(input)
+    }
+
+    fn part_one(&self, burrow: Burrow) -> Result<usize> {
+        part_one(burrow, self.log, self.explain)
+    }
+
+    fn part_two(&self, burrow: Burrow) -> Result<usize> {
+        part_two(burrow, self.log, self.explain)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "\
+#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########
+";
+
+    #[test]
+    fn part_one() {
+        let input = super::parse(INPUT).unwrap();
+        assert_eq!(super::part_one(input, Log::new(0), false).unwrap(), 12521);
+    }
+
+    #[test]
+    fn path_replays_to_goal() {
+        let burrow = super::parse(INPUT).unwrap();
+        let mut state = burrow.start.clone();
+        let (cost, moves) = solve(&burrow, Log::new(0)).unwrap();
+        for m in &moves {
+            state.apply(m);
+        }
+        assert_eq!(moves.iter().map(|m| m.energy).sum::<usize>(), cost);
+        assert!(state.hall.iter().all(|h| h.is_none()));
+        for (i, room) in state.rooms.iter().enumerate() {
+            assert!(room.occupants.iter().all(|f| f.0 == i));
+        }
+    }
+
+    #[test]
+    fn custom_shape() {
+        let input = "\
+#########
+#.......#
+###B#A###
+  #####
+";
+        let burrow = super::parse(input).unwrap();
+        assert_eq!(burrow.layout.doors, vec![2, 4]);
+        assert_eq!(burrow.layout.depth, 1);
+        assert_eq!(burrow.start.hall.len(), 7);
+        assert_eq!(
+            super::part_one(burrow.clone(), Log::new(0), false).unwrap(),
+            46
+        );
+        assert!(super::part_two(burrow, Log::new(0), false).is_err());
+    }
+
+    #[test]
+    fn partially_empty_rooms() {
+        let input = "\
+#########
+#...A...#
+###.#B###
+  #B#A#
+  #####
+";
+        let burrow = super::parse(input).unwrap();
+        assert_eq!(burrow.start.rooms[0].occupants, vec![Frog(1)]);
+        assert_eq!(burrow.start.hall[3], Some(Frog(0)));
+        assert!(burrow.clone().unfold().is_err());
+    }
+
+    #[test]
+    fn bad_burrows() {
+        let missing_room = "\
+#######
+#.....#
+###C#A###
+  #####
+";
+        assert!(parse(missing_room).is_err());
+
+        let gap = "\
+#######
+#.....#
+###B#A###
+  #.#B#
+  #####
+";
+        let error = format!("{:#}", parse(gap).unwrap_err());
+        assert_eq!(error, "room at column 3 has a gap");
+
+        // The energy of the twenty-first kind of amphipod, 10^20, does not fit.
+        let rooms = |n: u8| {
+            let letters = (0..n).map(|i| format!("#{}", (b'A' + i) as char));
+            let width = 2 * n as usize + 1;
+            format!(
+                "{}\n#{}#\n##{}###\n  {}\n",
+                "#".repeat(width + 2),
+                ".".repeat(width),
+                letters.collect::<String>(),
+                "#".repeat(width)
+            )
+        };
+        assert_eq!(parse(&rooms(20)).unwrap().start.rooms.len(), 20);
+        let error = format!("{:#}", parse(&rooms(21)).unwrap_err());
+        assert!(error.starts_with("too many rooms: 21 kinds"), "{}", error);
+    }
+}
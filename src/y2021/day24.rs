@@ -1,3 +1,4 @@
+use crate::log::Log;
 use crate::prelude::*;
 
 #[derive(Debug, Clone, Copy)]
@@ -69,7 +70,7 @@ fn parse_value(val: &str) -> Val {
     }
 }
 
-fn part_one(instructions: Vec<Instruction>) -> usize {
+fn part_one(instructions: Vec<Instruction>, log: Log) -> usize {
     let mut partitioned = Vec::new();
     let mut partition = Vec::new();
 
@@ -92,7 +93,7 @@ fn part_one(instructions: Vec<Instruction>) -> usize {
         .map(|c| c.to_digit(10).unwrap() as isize)
         .collect::<Vec<_>>();
     let out = exec(&instructions, &input, [0; 4]);
-    println!("{:?}", out);
+    log.debug(format_args!("{:?}", out));
     // for i in all_inputs().take(100) {
     // println!("{:?}", i);
     // }
@@ -131,6 +132,16 @@ fn part_two(_ins: Vec<Instruction>) -> usize {
     0
 }
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 24,
+    title: "Arithmetic Logic Unit",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
-    runner.run(parse, part_one, part_two);
+    let log = runner.log();
+    runner.run(parse, |ins| part_one(ins, log), part_two);
 }
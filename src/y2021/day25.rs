@@ -77,6 +77,15 @@ fn part_two(_board: Board) -> usize {
     0
 }
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 25,
+    title: "Sea Cucumber",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
     runner.run(parse, part_one, part_two);
 }
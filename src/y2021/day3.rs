@@ -26,8 +26,8 @@ fn part_one(nums: Vec<&str>) -> usize {
 }
 
 fn part_two(nums: Vec<&str>) -> usize {
-    let mut oxy = nums.iter().copied().collect::<Vec<_>>();
-    let mut co = nums.iter().copied().collect::<Vec<_>>();
+    let mut oxy = nums.to_vec();
+    let mut co = nums.to_vec();
     filter(&mut oxy, false);
     filter(&mut co, true);
     from_binary(oxy[0]) * from_binary(co[0])
@@ -61,6 +61,15 @@ fn from_binary(s: &str) -> usize {
     usize::from_str_radix(s, 2).unwrap()
 }
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 3,
+    title: "Binary Diagnostic",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
     runner.run(parse, part_one, part_two);
 }
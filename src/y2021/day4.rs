@@ -139,6 +139,15 @@ fn part_two(mut game: Game) -> usize {
     game.run_all()
 }
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 4,
+    title: "Giant Squid",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
     runner.run(parse, part_one, part_two);
 }
@@ -169,13 +178,13 @@ mod test {
 
     #[test]
     fn part_one() {
-        let mut input = super::parse(INPUT).expect("parse");
+        let input = super::parse(INPUT);
         assert_eq!(super::part_one(input), 4512)
     }
 
     #[test]
     fn part_two() {
-        let mut input = super::parse(INPUT).expect("parse");
+        let input = super::parse(INPUT);
         assert_eq!(super::part_two(input), 1924)
     }
 }
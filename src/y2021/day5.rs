@@ -72,6 +72,15 @@ fn part_two(lines: Vec<Line>) -> usize {
     counts.values().filter(|v| **v > 1).count()
 }
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 5,
+    title: "Hydrothermal Venture",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
     runner.run(parse, part_one, part_two);
 }
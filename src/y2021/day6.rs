@@ -31,6 +31,15 @@ fn iterate(fish: &mut [usize; 9], n_days: usize) {
     }
 }
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 6,
+    title: "Lanternfish",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
     runner.run(parse, part_one, part_two);
 }
@@ -0,0 +1,120 @@
+use crate::prelude::*;
+
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 7,
+    title: "The Treachery of Whales",
+    run,
+    generator: None,
+    exporter: None,
+};
+
+pub fn run(runner: &Runner) {
+    runner.solve(&[&Direct, &Scan]);
+}
+
+fn parse(input: &str) -> Result<Vec<usize>> {
+    let positions = parse_split(input, ',').context("expected comma-separated positions")?;
+    if positions.is_empty() {
+        return Err(anyhow!("no crab positions"));
+    }
+    Ok(positions)
+}
+
+/// Moves the crabs straight to the cheapest position: the median when every step costs the
+/// same, or next to the mean when each step costs more than the last.
+struct Direct;
+
+impl<'a> Solution<'a> for Direct {
+    type Input = Vec<usize>;
+
+    fn parse(&self, input: &'a str) -> Result<Vec<usize>> {
+        parse(input)
+    }
+
+    fn part_one(&self, mut input: Vec<usize>) -> Result<usize> {
+        let mid = input.len() / 2;
+        let (_, &mut median, _) = input.select_nth_unstable(mid);
+        Ok(fuel(&input, median, linear))
+    }
+
+    fn part_two(&self, input: Vec<usize>) -> Result<usize> {
+        let mean = input.iter().sum::<usize>() / input.len();
+        Ok(fuel(&input, mean, triangular).min(fuel(&input, mean + 1, triangular)))
+    }
+}
+
+/// Tries every position between the outermost crabs.
+struct Scan;
+
+impl<'a> Solution<'a> for Scan {
+    type Input = Vec<usize>;
+
+    fn name(&self) -> &'static str {
+        "scan"
+    }
+
+    fn parse(&self, input: &'a str) -> Result<Vec<usize>> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: Vec<usize>) -> Result<usize> {
+        Ok(scan(&input, linear))
+    }
+
+    fn part_two(&self, input: Vec<usize>) -> Result<usize> {
+        Ok(scan(&input, triangular))
+    }
+}
+
+fn linear(steps: usize) -> usize {
+    steps
+}
+
+fn triangular(steps: usize) -> usize {
+    steps * (steps + 1) / 2
+}
+
+fn fuel<F>(input: &[usize], target: usize, step_cost: F) -> usize
+where
+    F: Fn(usize) -> usize,
+{
+    input.iter().map(|&x| step_cost(x.abs_diff(target))).sum()
+}
+
+fn scan<F>(input: &[usize], step_cost: F) -> usize
+where
+    F: Fn(usize) -> usize + Copy,
+{
+    let min = input.iter().min().cloned().unwrap();
+    let max = input.iter().max().cloned().unwrap();
+    (min..=max)
+        .map(|t| fuel(input, t, step_cost))
+        .min()
+        .expect("nonempty")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "16,1,2,0,4,2,7,1,2,14";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(Direct.part_one(parse(INPUT).unwrap()).unwrap(), 37);
+        assert_eq!(Scan.part_one(parse(INPUT).unwrap()).unwrap(), 37);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(Direct.part_two(parse(INPUT).unwrap()).unwrap(), 168);
+        assert_eq!(Scan.part_two(parse(INPUT).unwrap()).unwrap(), 168);
+    }
+
+    #[test]
+    fn bad_input() {
+        assert!(parse("16,1,x").is_err());
+        assert!(parse("").is_err());
+    }
+}
@@ -18,6 +18,15 @@ static DIGITS: Lazy<HashMap<&'static str, usize>> = Lazy::new(|| {
     digits
 });
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 8,
+    title: "Seven Segment Search",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
     runner.run(parse, part_one, part_two);
 }
@@ -61,7 +70,7 @@ fn part_one(cases: Vec<Case>) -> usize {
         .flat_map(|c| c.output.iter())
         .map(|s| s.len())
         .filter(|&l| l == 2 || l == 3 || l == 4 || l == 7)
-        .sum()
+        .count()
 }
 
 fn part_two(cases: Vec<Case>) -> usize {
@@ -107,3 +116,28 @@ fn solve(seen: &[String]) -> Option<Vec<char>> {
         None
     }
 }
+
+#[cfg(test)]
+mod test {
+    const INPUT: &str = "\
+be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(super::part_one(super::parse(INPUT)), 26);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(super::part_two(super::parse(INPUT)), 61229);
+    }
+}
@@ -1,5 +1,14 @@
 use crate::prelude::*;
 
+pub const DAY: Day = Day {
+    year: 2021,
+    day: 9,
+    title: "Smoke Basin",
+    run,
+    generator: None,
+    exporter: None,
+};
+
 pub fn run(runner: &Runner) {
     runner.run(parse, part_one, part_two);
 }
@@ -9,7 +18,7 @@ fn parse(input: &str) -> Vec<Vec<usize>> {
         .lines()
         .map(|line| {
             line.chars()
-                .map(|c| ((c as usize) - ('0' as usize)))
+                .map(|c| (c as usize) - ('0' as usize))
                 .collect::<Vec<_>>()
         })
         .collect::<Vec<_>>()